@@ -0,0 +1,163 @@
+use solana_program::{
+    ed25519_program, instruction::Instruction, msg, program_error::ProgramError, pubkey::Pubkey,
+};
+
+// num_signatures (u8) + padding (u8)
+const SIGNATURE_OFFSETS_START: usize = 2;
+// signature, public key and message offsets (7 u16s)
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+
+/// Returns the public key and message of an Ed25519 program instruction verifying exactly one signature
+///
+/// The runtime has already checked the signature by the time this program runs, so only the layout is checked here
+pub fn verified_message(instruction: &Instruction) -> Result<(Pubkey, &[u8]), ProgramError> {
+    if !ed25519_program::check_id(&instruction.program_id) {
+        msg!("Passed instruction is not an Ed25519 program instruction");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let data = &instruction.data;
+    if data.first() != Some(&1) {
+        msg!("Ed25519 instruction has to verify exactly one signature");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let offsets = data
+        .get(SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let read_u16 = |index: usize| u16::from_le_bytes([offsets[index * 2], offsets[index * 2 + 1]]);
+
+    let signature_offset = read_u16(0) as usize;
+    let signature_instruction_index = read_u16(1);
+    let public_key_offset = read_u16(2) as usize;
+    let public_key_instruction_index = read_u16(3);
+    let message_data_offset = read_u16(4) as usize;
+    let message_data_size = read_u16(5) as usize;
+    let message_instruction_index = read_u16(6);
+
+    // Everything has to live in the Ed25519 instruction itself, otherwise the verified data could be another instruction's
+    if signature_instruction_index != u16::MAX
+        || public_key_instruction_index != u16::MAX
+        || message_instruction_index != u16::MAX
+    {
+        msg!("Ed25519 instruction has to carry its own signature, public key and message");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    data.get(signature_offset..signature_offset + SIGNATURE_LEN)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let public_key = data
+        .get(public_key_offset..public_key_offset + PUBKEY_LEN)
+        .map(Pubkey::new)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    Ok((public_key, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA_START: usize = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN;
+
+    // Lays the Ed25519 instruction out the way the Ed25519 program's own helper does
+    fn ed25519_instruction(
+        public_key: &Pubkey,
+        message: &[u8],
+        instruction_index: u16,
+    ) -> Instruction {
+        let public_key_offset = DATA_START;
+        let signature_offset = public_key_offset + PUBKEY_LEN;
+        let message_data_offset = signature_offset + SIGNATURE_LEN;
+
+        let mut data = vec![1, 0];
+        for offset in [
+            signature_offset as u16,
+            instruction_index,
+            public_key_offset as u16,
+            instruction_index,
+            message_data_offset as u16,
+            message.len() as u16,
+            instruction_index,
+        ] {
+            data.extend_from_slice(&offset.to_le_bytes());
+        }
+        data.extend_from_slice(&public_key.to_bytes());
+        data.extend_from_slice(&[7; SIGNATURE_LEN]);
+        data.extend_from_slice(message);
+
+        Instruction {
+            program_id: ed25519_program::id(),
+            accounts: vec![],
+            data,
+        }
+    }
+
+    #[test]
+    fn reads_the_public_key_and_message() {
+        let public_key = Pubkey::new_unique();
+        let instruction = ed25519_instruction(&public_key, b"signed order", u16::MAX);
+
+        let (signer_pubkey, message) = verified_message(&instruction).unwrap();
+        assert_eq!(signer_pubkey, public_key);
+        assert_eq!(message, b"signed order");
+    }
+
+    #[test]
+    fn rejects_other_programs() {
+        let mut instruction = ed25519_instruction(&Pubkey::new_unique(), b"order", u16::MAX);
+        instruction.program_id = Pubkey::new_unique();
+
+        assert_eq!(
+            verified_message(&instruction),
+            Err(ProgramError::IncorrectProgramId)
+        );
+    }
+
+    #[test]
+    fn rejects_anything_but_a_single_signature() {
+        let mut instruction = ed25519_instruction(&Pubkey::new_unique(), b"order", u16::MAX);
+        instruction.data[0] = 2;
+        assert_eq!(
+            verified_message(&instruction),
+            Err(ProgramError::InvalidInstructionData)
+        );
+
+        instruction.data[0] = 0;
+        assert_eq!(
+            verified_message(&instruction),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn rejects_data_living_in_other_instructions() {
+        let instruction = ed25519_instruction(&Pubkey::new_unique(), b"order", 0);
+
+        assert_eq!(
+            verified_message(&instruction),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn rejects_offsets_out_of_the_data() {
+        let mut instruction = ed25519_instruction(&Pubkey::new_unique(), b"order", u16::MAX);
+        instruction.data.truncate(instruction.data.len() - 1);
+        assert_eq!(
+            verified_message(&instruction),
+            Err(ProgramError::InvalidInstructionData)
+        );
+
+        instruction.data.truncate(DATA_START - 1);
+        assert_eq!(
+            verified_message(&instruction),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
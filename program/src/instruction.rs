@@ -1,6 +1,33 @@
 use std::convert::TryInto;
 
-use solana_program::program_error::ProgramError;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::state::{
+    Arbitration, CollectionCriteria, EscrowMode, Milestone, NftListing, RevenueSplit, StreamTerms,
+    VestingSchedule, XCustody,
+};
+
+/// Optional settings of `InitializeEscrow`, borsh serialized right after the expected Y token amount
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct EscrowOptions {
+    /// Recipients splitting the Y proceeds by basis points (should add up to 10000)
+    pub revenue_splits: Vec<RevenueSplit>,
+    /// Index of the revenue split recipient getting the rounding dust
+    pub dust_recipient_index: u8,
+    /// Fixed price (default), Dutch auction, English auction, sealed-bid auction, hash time lock or P2P fiat trade
+    pub mode: EscrowMode,
+    /// PDA owned temp X token account (default) or a delegation on Alice's X token account (fixed price and Dutch auctions only)
+    pub custody: XCustody,
+    /// Arbiter resolving disputes with a designated counterparty (fixed price escrows and Dutch auctions with a temp X account only)
+    ///
+    /// Required for P2P fiat trades, whose counterparty is left empty and becomes the buyer reserving the trade
+    pub arbitration: Option<Arbitration>,
+    /// NFT listing, checking the mint at init and exchange and paying its creators' royalties (X has to be a single unit of the NFT mint, fixed price escrows, Dutch auctions and bundles without arbiter only)
+    pub nft: Option<NftListing>,
+    /// Fresh mint the single receipt of the escrow gets minted from (fixed price escrows and Dutch auctions with a temp X account, no revenue splits and no arbiter only)
+    pub receipt_mint: Option<Pubkey>,
+}
 
 // inside instruction.rs
 pub enum EscrowInstruction {
@@ -9,14 +36,29 @@ pub enum EscrowInstruction {
     ///
     /// Accounts expected:
     ///
-    /// 0. `[signer]` Alice's account (signer coz this is the one who invokes the escrow)
-    /// 1. `[writable]` Alice's X Temporary account (should be created in prior, writable coz owbership transfer)
-    /// 2. `[]` Alice's Y Token Account
+    /// 0. `[signer]` Alice's account (signer coz this is the one who invokes the escrow, writable when the escrow account has to grow)
+    /// 1. `[writable]` Alice's X Temporary account (should be created in prior, writable coz owbership transfer), or her X Token Account approving the PDA as delegate for delegated custody
+    /// 2. `[]` Alice's Y Token Account (or a beneficiary's Y Token Account, which then receives the proceeds)
     /// 3. `[writable]` Escrow Account (Created prior as well)
     /// 4. Token program
+    /// 5. `[]` Rent refund recipient (optional, defaults to Alice's account, has to be passed when any accounts follow)
+    ///
+    /// English and sealed-bid auctions only:
+    ///
+    /// 6. `[writable]` Bid Vault (empty Y token account owned by Alice, ownership gets transferred to the PDA)
+    ///
+    /// For bundles, followed by the extra X Vaults (`[writable]`, funded token accounts owned by Alice in the order of the X legs, ownership gets transferred to the PDA) and Alice's Token Accounts receiving the extra Y legs (`[]`, in the order of the Y legs)
+    ///
+    /// For NFT listings, followed by the NFT Mint (`[]`) and the NFT Metadata Account (`[]`)
+    ///
+    /// For receipt escrows, followed by the Receipt Mint (`[writable]`, with 0 decimals, no supply, no freeze authority and Alice as mint authority, which gets revoked) and Alice's Token Account of the Receipt Mint (`[writable]`)
+    ///
+    /// When the escrow account is too small for the state, followed by the System Program (`[]`), the account gets reallocated and Alice tops its rent up
     InitializeEscrow {
-        /// Token Y amount Alice expects
+        /// Token Y amount Alice expects (unused when the price comes from an auction)
         expected_y_token_amount: u64,
+        /// Optional settings (defaults when nothing follows the amount), boxed as they outweigh every other instruction
+        options: Box<EscrowOptions>,
     },
 
     /// Accepts a trade
@@ -24,29 +66,858 @@ pub enum EscrowInstruction {
     ///
     /// Accounts expected:
     ///
-    /// 0. `[signer]` Bob's account (signer coz this is the one who takes the amount)
+    /// 0. `[signer, writable]` Bob's account (signer coz this is the one who takes the amount, writable coz he pays the rent if his X token account gets created)
     /// 1. `[writable]` Bob's Y Token Account
-    /// 2. `[writable]` Bob's X Token Account
-    /// 3. `[writable]` PDA's Temp X Token Account (previously from Alice)
-    /// 4. `[writable]` Alice's account (because rent fees are sent back once the temp token account and escrow state account are closed)
-    /// 5. `[writable]` Alice's Y Token Account
+    /// 2. `[writable]` Bob's X Token Account (owned by Bob, or his associated token account which gets created if it doesn't exist yet)
+    /// 3. `[writable]` PDA's Temp X Token Account (previously from Alice), or Alice's X Token Account for delegated custody
+    /// 4. `[writable]` Rent refund recipient stored in the escrow state, Alice's account by default (because rent fees are sent back once the temp token account and escrow state account are closed)
+    /// 5. `[writable]` Alice's Y Token Account (the one stored in the escrow state)
     /// 6. `[writable]` Escrow State Account
     /// 7. `[]` Token Program
     /// 8. `[]` PDA of Escrow Program
+    ///
+    /// Only needed when Bob's associated X token account has to be created:
+    ///
+    /// 9. `[]` X Token Mint
+    /// 10. `[]` System Program
+    /// 11. `[]` Associated Token Account Program
+    /// 12. `[]` Rent Sysvar
+    ///
+    /// For receipt escrows, followed by the Receipt Token Account of the current holder (`[]`), the Y proceeds going to the holder's Y Token Account passed as Alice's
+    ///
+    /// For NFT listings, followed by the NFT Metadata Account and the Y Token Accounts of its creators (`[writable]`, in the metadata's order), who get their royalties out of the Y price
+    ///
+    /// Followed by the Y Token Accounts of the revenue split recipients (`[writable]`, in the order stored in the escrow state) if the Y proceeds are split
+    ///
+    /// For bundles, followed by a `[writable]` pair per extra X leg (the PDA's X Vault, then Bob's Token Account receiving it) and per extra Y leg (Bob's Token Account paying it, then Alice's Token Account stored in the leg)
     ExchangeEscrow { expected_x_token_amount: u64 },
 
     /// Cancels an ongoing trade
     ///
+    /// Hash time locked escrows can only be cancelled (refunded) once their timelock has passed, P2P fiat trades only while open
+    ///
     ///
     /// Accounts expected:
     ///
-    /// 0. `[signer]` Alice's Account
+    /// 0. `[signer, writable]` Alice's Account
     /// 1. `[writable]` Escrow State Account
-    /// 2. `[writable]` Temporary Token X Account
+    /// 2. `[writable]` Temporary Token X Account (Alice's X Token Account for delegated custody, left untouched)
     /// 3. `[writable]` Alice Token X Account
     /// 4. `[]` Token Program
     /// 5. `[]` PDA of Escrow Program
+    /// 6. `[writable]` Rent refund recipient (optional when it is Alice's account, has to be passed for auctions, bundles and receipt escrows)
+    /// 7. `[writable]` Bid Vault (English and sealed-bid auctions only, which can only be cancelled before the first bid)
+    /// 8. `[writable]` Alice's Y Token Account (auctions only, gets whatever was sent to the Bid Vault without bidding)
+    ///
+    /// For receipt escrows, the caller is the receipt holder rather than Alice, followed by their Receipt Token Account (`[writable]`) and the Receipt Mint (`[writable]`), the receipt getting burnt
+    ///
+    /// For bundles, followed by a `[writable]` pair per extra X leg (the PDA's X Vault, then Alice's Token Account getting it back)
     CancelEscrow,
+
+    /// Logs the Y price Bob would have to pay right now (changes over time for Dutch auctions)
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[]` Escrow State Account
+    QuoteEscrowPrice,
+
+    /// Places a bid on an English auction, refunding the previous highest bidder
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Bidder's account (writable coz it pays the rent if the previous bidder's refund account gets created)
+    /// 1. `[writable]` Bidder's Y Token Account
+    /// 2. `[writable]` Escrow State Account
+    /// 3. `[writable]` Bid Vault
+    /// 4. `[]` Token Program
+    /// 5. `[]` PDA of Escrow Program
+    /// 6. `[writable]` Previous highest bidder's associated Y Token Account (only when there is a bid to beat)
+    ///
+    /// Only needed when the previous highest bidder's associated Y token account has to be created:
+    ///
+    /// 7. `[]` Previous highest bidder's account
+    /// 8. `[]` Y Token Mint
+    /// 9. `[]` System Program
+    /// 10. `[]` Associated Token Account Program
+    /// 11. `[]` Rent Sysvar
+    PlaceBid { bid_amount: u64 },
+
+    /// Settles an English auction once it has ended (or a sealed-bid auction once its reveal window has ended), callable by anyone
+    ///
+    /// Sealed-bid deposits are refunded afterwards through `RefundSealedBid`, the bid vault and escrow state get closed with the last one
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Settler's account (writable coz it pays the rent if the winner's X token account gets created)
+    /// 1. `[writable]` Escrow State Account
+    /// 2. `[writable]` PDA's Temp X Token Account
+    /// 3. `[writable]` Bid Vault
+    /// 4. `[writable]` Winner's associated X Token Account (or Alice's X Token Account if there were no bids)
+    /// 5. `[writable]` Alice's Y Token Account (the one stored in the escrow state)
+    /// 6. `[writable]` Rent refund recipient stored in the escrow state
+    /// 7. `[]` Token Program
+    /// 8. `[]` PDA of Escrow Program
+    ///
+    /// Only needed when the winner's associated X token account has to be created:
+    ///
+    /// 9. `[]` Winner's account
+    /// 10. `[]` X Token Mint
+    /// 11. `[]` System Program
+    /// 12. `[]` Associated Token Account Program
+    /// 13. `[]` Rent Sysvar
+    ///
+    /// Followed by the Y Token Accounts of the revenue split recipients (`[writable]`, in the order stored in the escrow state) if the Y proceeds are split
+    SettleAuction,
+
+    /// Commits a hashed bid to a sealed-bid auction, locking the deposit in the bid vault
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Bidder's account (writable coz it pays the rent of the sealed bid account)
+    /// 1. `[writable]` Bidder's Y Token Account
+    /// 2. `[writable]` Escrow State Account
+    /// 3. `[writable]` Bid Vault
+    /// 4. `[writable]` Sealed Bid Account (PDA of `["sealed_bid", escrow state, bidder]`, created here)
+    /// 5. `[]` Token Program
+    /// 6. `[]` System Program
+    CommitSealedBid {
+        /// See `SealedBidState::commitment_for`
+        commitment: [u8; 32],
+        /// Y locked until the auction is settled, the revealed bid can't be more than this
+        deposit: u64,
+    },
+
+    /// Reveals a committed bid once the commit window has ended
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Bidder's account
+    /// 1. `[writable]` Escrow State Account
+    /// 2. `[writable]` Sealed Bid Account
+    RevealSealedBid { bid_amount: u64, salt: [u8; 32] },
+
+    /// Refunds (or forfeits to Alice when unrevealed and configured so) the deposit of a sealed bid once the auction is settled, callable by anyone
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Caller's account (writable coz it pays the rent if the bidder's Y token account gets created)
+    /// 1. `[writable]` Escrow State Account
+    /// 2. `[writable]` Sealed Bid Account
+    /// 3. `[writable]` Bidder's account (gets the sealed bid account's rent back)
+    /// 4. `[writable]` Bid Vault
+    /// 5. `[writable]` Bidder's associated Y Token Account
+    /// 6. `[writable]` Alice's Y Token Account (the one stored in the escrow state)
+    /// 7. `[writable]` Rent refund recipient stored in the escrow state
+    /// 8. `[]` Token Program
+    /// 9. `[]` PDA of Escrow Program
+    ///
+    /// Only needed when the bidder's associated Y token account has to be created:
+    ///
+    /// 10. `[]` Y Token Mint
+    /// 11. `[]` System Program
+    /// 12. `[]` Associated Token Account Program
+    /// 13. `[]` Rent Sysvar
+    RefundSealedBid,
+
+    /// Releases the X tokens of a hash time locked escrow to its recipient before the timelock, callable by anyone holding the preimage
+    ///
+    /// The preimage gets logged so that the counterparty chain can use it
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Claimer's account (writable coz it pays the rent if the recipient's X token account gets created)
+    /// 1. `[writable]` Escrow State Account
+    /// 2. `[writable]` PDA's Temp X Token Account
+    /// 3. `[writable]` Recipient's associated X Token Account
+    /// 4. `[writable]` Rent refund recipient stored in the escrow state
+    /// 5. `[]` Token Program
+    /// 6. `[]` PDA of Escrow Program
+    ///
+    /// Only needed when the recipient's associated X token account has to be created:
+    ///
+    /// 7. `[]` Recipient's account
+    /// 8. `[]` X Token Mint
+    /// 9. `[]` System Program
+    /// 10. `[]` Associated Token Account Program
+    /// 11. `[]` Rent Sysvar
+    ClaimWithPreimage { preimage: Vec<u8> },
+
+    /// Fills a maker's off-chain signed order, without the maker having to initialize an escrow beforehand
+    ///
+    /// Has to come right after an Ed25519 program instruction verifying the maker's signature over the borsh serialized `SignedOrder`
+    /// X is pulled from the maker's X token account through the delegation given to the maker's order PDA, kept apart from the escrow PDA delegated escrows use
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Taker's account (writable coz it pays the rent if the maker's nonce account gets created)
+    /// 1. `[writable]` Taker's Y Token Account
+    /// 2. `[writable]` Taker's X Token Account
+    /// 3. `[writable]` Maker's X Token Account (the one in the order)
+    /// 4. `[writable]` Maker's Y Token Account (the one in the order)
+    /// 5. `[writable]` Maker's Nonce Account (PDA of `["nonce", maker]`, created when missing)
+    /// 6. `[]` Maker's Order PDA (PDA of `["order", maker]`, the X delegate)
+    /// 7. `[]` Instructions Sysvar
+    /// 8. `[]` Token Program
+    /// 9. `[]` System Program
+    ExchangeSignedOrder,
+
+    /// Bumps the maker's nonce, invalidating every signed order carrying the current one
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Maker's account (writable coz it pays the rent if the nonce account gets created)
+    /// 1. `[writable]` Maker's Nonce Account (PDA of `["nonce", maker]`, created when missing)
+    /// 2. `[]` System Program
+    IncrementMakerNonce,
+
+    /// Locks the payer's deposit for a contract, released to the payee milestone by milestone
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Payer's account
+    /// 1. `[writable]` Payer's Token Account (the deposit is taken from here)
+    /// 2. `[]` Payee's Token Account (same mint, receives the tranches)
+    /// 3. `[writable]` Vault (empty token account owned by the payer, ownership gets transferred to the PDA)
+    /// 4. `[writable]` Milestone Escrow State Account (Created prior, owned by this program)
+    /// 5. `[]` Token Program
+    InitializeMilestoneEscrow {
+        /// Unreleased tranches become refundable from then on
+        final_deadline: i64,
+        /// Tranches in release order, their amounts add up to the deposit
+        milestones: Vec<Milestone>,
+    },
+
+    /// Releases one tranche to the payee, the vault and state get closed with the last one
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Payer's account (writable coz it gets the rent back after the last tranche)
+    /// 1. `[writable]` Milestone Escrow State Account
+    /// 2. `[writable]` Vault
+    /// 3. `[writable]` Payee's Token Account (the one stored in the state)
+    /// 4. `[]` Token Program
+    /// 5. `[]` PDA of `["milestone", milestone escrow state]`
+    ReleaseMilestone { milestone_index: u8 },
+
+    /// Refunds every unreleased tranche to the payer once the final deadline has passed, closing the vault and state
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Payer's account
+    /// 1. `[writable]` Milestone Escrow State Account
+    /// 2. `[writable]` Vault
+    /// 3. `[writable]` Payer's Token Account
+    /// 4. `[]` Token Program
+    /// 5. `[]` PDA of `["milestone", milestone escrow state]`
+    RefundMilestones,
+
+    /// Freezes an escrow with an arbiter, callable by Alice or the counterparty
+    ///
+    /// A disputed escrow can no longer be exchanged or cancelled, only resolved by the arbiter
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Alice's or the counterparty's account
+    /// 1. `[writable]` Escrow State Account
+    RaiseDispute,
+
+    /// Splits the X tokens of a disputed escrow between Alice and the counterparty after taking the arbiter fee
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Arbiter's account
+    /// 1. `[writable]` Escrow State Account
+    /// 2. `[writable]` PDA's Temp X Token Account
+    /// 3. `[writable]` Alice's X Token Account
+    /// 4. `[writable]` Counterparty's X Token Account
+    /// 5. `[writable]` Arbiter's X Token Account
+    /// 6. `[writable]` Rent refund recipient stored in the escrow state
+    /// 7. `[]` Token Program
+    /// 8. `[]` PDA of Escrow Program
+    Resolve {
+        /// Share of what is left after the arbiter fee going to Alice, the counterparty gets the rest
+        alice_basis_points: u16,
+    },
+
+    /// Reserves an open P2P fiat trade, the buyer then has the payment window to send the fiat payment
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Buyer's account
+    /// 1. `[writable]` Escrow State Account
+    ReserveFiatTrade,
+
+    /// Marks the fiat payment of a reserved trade as sent, Alice then has the confirmation window to release X
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Buyer's account
+    /// 1. `[writable]` Escrow State Account
+    MarkFiatPaid,
+
+    /// Confirms the fiat payment of a reserved trade, releasing X to the buyer
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Alice's account (writable coz it pays the rent if the buyer's X token account gets created)
+    /// 1. `[writable]` Escrow State Account
+    /// 2. `[writable]` PDA's Temp X Token Account
+    /// 3. `[writable]` Buyer's associated X Token Account
+    /// 4. `[writable]` Rent refund recipient stored in the escrow state
+    /// 5. `[]` Token Program
+    /// 6. `[]` PDA of Escrow Program
+    ///
+    /// Only needed when the buyer's associated X token account has to be created:
+    ///
+    /// 7. `[]` Buyer's account
+    /// 8. `[]` X Token Mint
+    /// 9. `[]` System Program
+    /// 10. `[]` Associated Token Account Program
+    /// 11. `[]` Rent Sysvar
+    ReleaseFiatTrade,
+
+    /// Applies the timeout of the current step of a P2P fiat trade, callable by anyone
+    ///
+    /// An unpaid reservation reopens the trade, an unconfirmed payment escalates the trade to the arbiter
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` Escrow State Account
+    TimeoutFiatTrade,
+
+    /// Locks the depositor's tokens for a beneficiary, unlocked following the vesting schedule
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Depositor's account
+    /// 1. `[writable]` Depositor's Token Account (the total amount is taken from here)
+    /// 2. `[]` Beneficiary's account
+    /// 3. `[writable]` Vault (empty token account owned by the depositor, ownership gets transferred to the PDA)
+    /// 4. `[writable]` Vesting State Account (Created prior with `VestingState::LEN` bytes, owned by this program)
+    /// 5. `[]` Token Program
+    InitializeVesting { schedule: VestingSchedule },
+
+    /// Withdraws everything vested so far to the beneficiary, the vault and state get closed once nothing is left
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Beneficiary's account
+    /// 1. `[writable]` Vesting State Account
+    /// 2. `[writable]` Vault
+    /// 3. `[writable]` Beneficiary's Token Account
+    /// 4. `[writable]` Depositor's account (gets the rent back once nothing is left)
+    /// 5. `[]` Token Program
+    /// 6. `[]` PDA of `["vesting", vesting state]`
+    WithdrawVested,
+
+    /// Takes the unvested tokens of a revocable vesting back, what has vested so far stays withdrawable by the beneficiary
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Depositor's account (writable coz it gets the rent back if nothing is left)
+    /// 1. `[writable]` Vesting State Account
+    /// 2. `[writable]` Vault
+    /// 3. `[writable]` Depositor's Token Account
+    /// 4. `[]` Token Program
+    /// 5. `[]` PDA of `["vesting", vesting state]`
+    RevokeVesting,
+
+    /// Deposits the sender's tokens into a stream flowing to the recipient by the second
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Sender's account
+    /// 1. `[writable]` Sender's Token Account (the deposit is taken from here)
+    /// 2. `[]` Recipient's Token Account (same mint, receives the streamed tokens)
+    /// 3. `[writable]` Vault (empty token account owned by the sender, ownership gets transferred to the PDA)
+    /// 4. `[writable]` Stream State Account (Created prior with `StreamState::LEN` bytes, owned by this program)
+    /// 5. `[]` Token Program
+    InitializeStream { terms: StreamTerms },
+
+    /// Withdraws everything streamed so far to the recipient, the vault and state get closed once the deposit is exhausted
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Recipient's account
+    /// 1. `[writable]` Stream State Account
+    /// 2. `[writable]` Vault
+    /// 3. `[writable]` Recipient's Token Account (the one stored in the state)
+    /// 4. `[writable]` Sender's account (gets the rent back once the deposit is exhausted)
+    /// 5. `[]` Token Program
+    /// 6. `[]` PDA of `["stream", stream state]`
+    WithdrawStreamed,
+
+    /// Stops a stream, paying what has been streamed to the recipient and the rest back to the sender, callable by either party
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Sender's or recipient's account
+    /// 1. `[writable]` Stream State Account
+    /// 2. `[writable]` Vault
+    /// 3. `[writable]` Recipient's Token Account (the one stored in the state)
+    /// 4. `[writable]` Sender's Token Account
+    /// 5. `[writable]` Sender's account (gets the rent back)
+    /// 6. `[]` Token Program
+    /// 7. `[]` PDA of `["stream", stream state]`
+    CloseStream,
+
+    /// Starts a crowdfunding campaign pooling Y towards a goal until the deadline
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Creator's account
+    /// 1. `[]` Creator's Y Token Account (the pooled Y is claimed here)
+    /// 2. `[writable]` Vault (empty Y token account owned by the creator, ownership gets transferred to the PDA)
+    /// 3. `[writable]` Campaign State Account (Created prior with `CampaignState::LEN` bytes, owned by this program)
+    /// 4. `[]` Token Program
+    InitializeCampaign { goal_amount: u64, deadline: i64 },
+
+    /// Deposits Y into a campaign before its deadline, recorded in the backer's contribution receipt
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Backer's account (writable coz it pays the rent of the contribution receipt)
+    /// 1. `[writable]` Backer's Y Token Account
+    /// 2. `[writable]` Campaign State Account
+    /// 3. `[writable]` Vault
+    /// 4. `[writable]` Contribution Receipt (PDA of `["contribution", campaign state, backer]`, created on the first deposit)
+    /// 5. `[]` Token Program
+    /// 6. `[]` System Program
+    Contribute { amount: u64 },
+
+    /// Claims the pooled Y of a campaign which met its goal once the deadline has passed, or closes a failed campaign nobody backed
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Creator's account (writable coz it gets the rent of the vault back)
+    /// 1. `[writable]` Campaign State Account
+    /// 2. `[writable]` Vault
+    /// 3. `[writable]` Creator's Y Token Account (the one stored in the state)
+    /// 4. `[]` Token Program
+    /// 5. `[]` PDA of `["campaign", campaign state]`
+    ClaimCampaign,
+
+    /// Closes a backer's contribution receipt once the deadline has passed, refunding the deposit if the goal was missed
+    ///
+    /// The campaign (and its vault if still open) gets closed with the last receipt
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Backer's account (gets the receipt's rent back)
+    /// 1. `[writable]` Campaign State Account
+    /// 2. `[writable]` Contribution Receipt
+    /// 3. `[writable]` Vault
+    /// 4. `[writable]` Backer's Y Token Account
+    /// 5. `[writable]` Creator's account (gets the campaign's rent back)
+    /// 6. `[]` Token Program
+    /// 7. `[]` PDA of `["campaign", campaign state]`
+    /// 8. `[writable]` Creator's Y Token Account (the one stored in the state, gets whatever else was sent to the vault once the last deposit of a failed campaign is refunded)
+    RefundContribution,
+
+    /// Escrows a bounty reward which any of the approvers can award
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Poster's account
+    /// 1. `[writable]` Poster's Token Account (the reward is taken from here)
+    /// 2. `[writable]` Vault (empty token account owned by the poster, ownership gets transferred to the PDA)
+    /// 3. `[writable]` Bounty State Account (Created prior, owned by this program)
+    /// 4. `[]` Token Program
+    InitializeBounty {
+        reward_amount: u64,
+        /// The poster can reclaim the reward from then on if nothing was awarded
+        expiry_timestamp: i64,
+        approvers: Vec<Pubkey>,
+    },
+
+    /// Pays the reward out to one or more hunters, closing the bounty
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Approver's account
+    /// 1. `[writable]` Bounty State Account
+    /// 2. `[writable]` Vault
+    /// 3. `[writable]` Poster's account (gets the rent back)
+    /// 4. `[]` Token Program
+    /// 5. `[]` PDA of `["bounty", bounty state]`
+    ///
+    /// Followed by the Token Accounts of the hunters (`[writable]`, in the order of `hunter_basis_points`)
+    Award {
+        /// Share of every hunter, adding up to 10000 (the last hunter gets the rounding dust)
+        hunter_basis_points: Vec<u16>,
+    },
+
+    /// Takes the reward of an expired bounty back, closing the bounty
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Poster's account
+    /// 1. `[writable]` Bounty State Account
+    /// 2. `[writable]` Vault
+    /// 3. `[writable]` Poster's Token Account
+    /// 4. `[]` Token Program
+    /// 5. `[]` PDA of `["bounty", bounty state]`
+    ReclaimBounty,
+
+    /// Bids Y tokens for any NFT of a collection, the collection being a Merkle root of the allowed mints or a verified Metaplex collection
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Buyer's account
+    /// 1. `[writable]` Buyer's Y Token Account (the bid is taken from here)
+    /// 2. `[writable]` Vault (empty token account owned by the buyer, ownership gets transferred to the PDA)
+    /// 3. `[writable]` Collection Bid State Account (Created prior, owned by this program)
+    /// 4. `[]` Token Program
+    InitializeCollectionBid {
+        bid_amount: u64,
+        criteria: CollectionCriteria,
+    },
+
+    /// Sells an NFT of the collection to the buyer for the bid, closing the bid
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Seller's account (writable coz it pays the rent if the buyer's NFT token account gets created)
+    /// 1. `[writable]` Seller's NFT Token Account
+    /// 2. `[writable]` Seller's Y Token Account
+    /// 3. `[writable]` Buyer's associated NFT Token Account
+    /// 4. `[writable]` Collection Bid State Account
+    /// 5. `[writable]` Vault
+    /// 6. `[writable]` Buyer's account (gets the rent back)
+    /// 7. `[]` NFT Mint
+    /// 8. `[]` Token Program
+    /// 9. `[]` PDA of `["collection_bid", bid state]`
+    ///
+    /// Only needed when the bid is for a verified collection:
+    ///
+    /// 10. `[]` NFT Metadata Account
+    ///
+    /// Only needed when the buyer's associated NFT token account has to be created:
+    ///
+    /// 11. `[]` Buyer's account
+    /// 12. `[]` NFT Mint
+    /// 13. `[]` System Program
+    /// 14. `[]` Associated Token Account Program
+    /// 15. `[]` Rent Sysvar
+    FillCollectionBid {
+        /// Proof of the NFT mint's membership, empty when the bid is for a verified collection
+        merkle_proof: Vec<[u8; 32]>,
+    },
+
+    /// Takes the bid back, closing it
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Buyer's account
+    /// 1. `[writable]` Collection Bid State Account
+    /// 2. `[writable]` Vault
+    /// 3. `[writable]` Buyer's Y Token Account
+    /// 4. `[]` Token Program
+    /// 5. `[]` PDA of `["collection_bid", bid state]`
+    CancelCollectionBid,
+
+    /// Sets up an OTC trade where the maker deposits X and the taker deposits Y, each in their own transaction
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Maker's account
+    /// 1. `[writable]` X Vault (empty X token account owned by the maker, ownership gets transferred to the PDA)
+    /// 2. `[writable]` Y Vault (empty Y token account owned by the maker, ownership gets transferred to the PDA)
+    /// 3. `[writable]` Mutual Escrow State Account (Created prior, owned by this program)
+    /// 4. `[]` Token Program
+    InitializeMutualEscrow {
+        x_token_amount: u64,
+        y_token_amount: u64,
+        taker_pubkey: Pubkey,
+    },
+
+    /// Deposits the caller's leg, X for the maker and Y for the taker
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Maker's or taker's account
+    /// 1. `[writable]` Caller's Token Account the leg is taken from
+    /// 2. `[writable]` Mutual Escrow State Account
+    /// 3. `[writable]` Vault of the caller's leg
+    /// 4. `[]` Token Program
+    DepositMutualEscrow,
+
+    /// Takes the caller's deposit back, only possible until both legs are deposited
+    ///
+    /// The maker withdrawing while the taker has no deposit closes the escrow
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Maker's or taker's account (writable coz the maker gets the rent back)
+    /// 1. `[writable]` Mutual Escrow State Account
+    /// 2. `[writable]` X Vault
+    /// 3. `[writable]` Y Vault
+    /// 4. `[writable]` Caller's Token Account getting the deposit back (and the X vault's leftovers when the maker closes the escrow)
+    /// 5. `[]` Token Program
+    /// 6. `[]` PDA of `["mutual", mutual escrow state]`
+    ///
+    /// Only needed when the maker closes the escrow:
+    ///
+    /// 7. `[writable]` Maker's associated Y Token Account (gets the Y vault's leftovers, created when missing)
+    ///
+    /// Only needed when the maker's associated Y token account has to be created:
+    ///
+    /// 8. `[]` Maker's account
+    /// 9. `[]` Y Token Mint
+    /// 10. `[]` System Program
+    /// 11. `[]` Associated Token Account Program
+    /// 12. `[]` Rent Sysvar
+    WithdrawMutualDeposit,
+
+    /// Swaps both deposited legs into the parties' associated token accounts, callable by either party
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Maker's or taker's account (writable coz it pays the rent if an associated token account gets created)
+    /// 1. `[writable]` Mutual Escrow State Account
+    /// 2. `[writable]` X Vault
+    /// 3. `[writable]` Y Vault
+    /// 4. `[writable]` Taker's associated X Token Account
+    /// 5. `[writable]` Maker's associated Y Token Account
+    /// 6. `[writable]` Maker's account (gets the rent back)
+    /// 7. `[]` Token Program
+    /// 8. `[]` PDA of `["mutual", mutual escrow state]`
+    ///
+    /// Only needed when the taker's associated X token account has to be created:
+    ///
+    /// 9. `[]` Taker's account
+    /// 10. `[]` X Token Mint
+    /// 11. `[]` System Program
+    /// 12. `[]` Associated Token Account Program
+    /// 13. `[]` Rent Sysvar
+    ///
+    /// Followed by the same accounts for the maker's associated Y token account (the maker's account and the Y Token Mint) when it has to be created
+    SettleMutualEscrow,
+
+    /// Writes a covered call by locking X, to be sold for a premium and exercised at the strike until expiry
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Writer's account
+    /// 1. `[writable]` Writer's X Token Account (the covered X is taken from here)
+    /// 2. `[]` Y Token Mint (the premium and the strike are paid in, into the writer's associated token account)
+    /// 3. `[writable]` X Vault (empty token account owned by the writer, ownership gets transferred to the PDA)
+    /// 4. `[writable]` Holder Mint (new mint with 0 decimals and no freeze authority, the writer's mint authority gets transferred to the PDA)
+    /// 5. `[writable]` Call Option State Account (Created prior, owned by this program)
+    /// 6. `[]` Token Program
+    WriteCallOption {
+        x_token_amount: u64,
+        premium_amount: u64,
+        strike_amount: u64,
+        expiry_timestamp: i64,
+    },
+
+    /// Pays the premium of an unsold call option, minting the holder token to the buyer
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Buyer's account (writable coz it pays the rent if its holder token account gets created)
+    /// 1. `[writable]` Buyer's Y Token Account
+    /// 2. `[writable]` Writer's associated Y Token Account (created when missing)
+    /// 3. `[writable]` Buyer's associated Holder Token Account
+    /// 4. `[writable]` Call Option State Account
+    /// 5. `[writable]` Holder Mint
+    /// 6. `[]` Token Program
+    /// 7. `[]` PDA of `["option", call option state]`
+    ///
+    /// Only needed when the buyer's associated holder token account has to be created:
+    ///
+    /// 8. `[]` Buyer's account
+    /// 9. `[]` Holder Mint
+    /// 10. `[]` System Program
+    /// 11. `[]` Associated Token Account Program
+    /// 12. `[]` Rent Sysvar
+    ///
+    /// Followed by the same accounts for the writer's associated Y token account (the writer's account and the Y Token Mint) when it has to be created
+    BuyCallOption,
+
+    /// Buys the covered X at the strike before expiry, burning the holder token
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Holder's account (writable coz it pays the rent if the writer's Y token account gets created)
+    /// 1. `[writable]` Holder's Holder Token Account
+    /// 2. `[writable]` Holder's Y Token Account (the strike is taken from here)
+    /// 3. `[writable]` Holder's X Token Account
+    /// 4. `[writable]` Writer's associated Y Token Account (created when missing)
+    /// 5. `[writable]` Call Option State Account
+    /// 6. `[writable]` X Vault
+    /// 7. `[writable]` Holder Mint
+    /// 8. `[writable]` Writer's account (gets the rent back)
+    /// 9. `[]` Token Program
+    /// 10. `[]` PDA of `["option", call option state]`
+    ///
+    /// Only needed when the writer's associated Y token account has to be created:
+    ///
+    /// 11. `[]` Writer's account
+    /// 12. `[]` Y Token Mint
+    /// 13. `[]` System Program
+    /// 14. `[]` Associated Token Account Program
+    /// 15. `[]` Rent Sysvar
+    ExerciseCallOption,
+
+    /// Takes the covered X back once the option has expired (or any time before it is sold), closing the option
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Writer's account
+    /// 1. `[writable]` Call Option State Account
+    /// 2. `[writable]` X Vault
+    /// 3. `[writable]` Writer's X Token Account
+    /// 4. `[]` Token Program
+    /// 5. `[]` PDA of `["option", call option state]`
+    ReclaimCallOption,
+
+    /// Hands an escrow over to a new maker, who gets the proceeds, the rent and the right to cancel from then on
+    ///
+    /// The X held by the escrow stays in place, its vaults being moved under the new maker's PDA. Revenue split recipients stay as they are
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Alice's account
+    /// 1. `[]` New maker's account
+    /// 2. `[]` Alice's Y Token Account (the one stored in the escrow state)
+    /// 3. `[]` New maker's Y Token Account (gets the proceeds from then on)
+    /// 4. `[]` New rent refund recipient
+    /// 5. `[writable]` Escrow State Account
+    /// 6. `[writable]` PDA's Temp X Token Account
+    /// 7. `[]` Token Program
+    /// 8. `[]` PDA of Escrow Program (the current one, derived from Alice)
+    /// 9. `[writable]` Bid Vault (English and sealed-bid auctions only)
+    ///
+    /// For bundles, followed by the extra X Vaults (`[writable]`, in the order of the X legs), then for each Y leg in order, its current Token Account (`[]`) and the new maker's Token Account receiving it from then on (`[]`, same mint, same owner as the new maker's Y Token Account)
+    TransferEscrowOwnership,
+
+    /// Locks X collateral and asks for a Y loan, to be repaid with interest within the loan duration once funded
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Borrower's account
+    /// 1. `[writable]` Borrower's X Token Account (the collateral is taken from here)
+    /// 2. `[]` Borrower's Y Token Account (gets the principal)
+    /// 3. `[writable]` Collateral Vault (empty token account owned by the borrower, ownership gets transferred to the PDA)
+    /// 4. `[writable]` Loan State Account (Created prior, owned by this program)
+    /// 5. `[]` Token Program
+    RequestLoan {
+        collateral_amount: u64,
+        principal_amount: u64,
+        interest_amount: u64,
+        /// Seconds the borrower has to repay once funded
+        loan_duration: i64,
+    },
+
+    /// Lends the principal to the borrower, starting the loan
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Lender's account
+    /// 1. `[writable]` Lender's Y Token Account (the principal is taken from here, the repayment goes to the lender's associated token account)
+    /// 2. `[writable]` Borrower's Y Token Account (the one stored in the state)
+    /// 3. `[writable]` Loan State Account
+    /// 4. `[]` Collateral Vault
+    /// 5. `[]` Token Program
+    /// 6. `[]` PDA of `["loan", loan state]`
+    FundLoan,
+
+    /// Repays the principal and the interest before the deadline, giving the collateral back and closing the loan
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Borrower's account
+    /// 1. `[writable]` Borrower's Y Token Account the repayment is taken from
+    /// 2. `[writable]` Lender's associated Y Token Account (created when missing)
+    /// 3. `[writable]` Borrower's X Token Account (gets the collateral back)
+    /// 4. `[writable]` Loan State Account
+    /// 5. `[writable]` Collateral Vault
+    /// 6. `[]` Token Program
+    /// 7. `[]` PDA of `["loan", loan state]`
+    ///
+    /// Only needed when the lender's associated Y token account has to be created:
+    ///
+    /// 8. `[]` Lender's account
+    /// 9. `[]` Y Token Mint
+    /// 10. `[]` System Program
+    /// 11. `[]` Associated Token Account Program
+    /// 12. `[]` Rent Sysvar
+    RepayLoan,
+
+    /// Takes the collateral of a loan which wasn't repaid by the deadline, closing the loan
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` Lender's account
+    /// 1. `[writable]` Lender's X Token Account
+    /// 2. `[writable]` Loan State Account
+    /// 3. `[writable]` Collateral Vault
+    /// 4. `[writable]` Borrower's account (gets the rent back)
+    /// 5. `[]` Token Program
+    /// 6. `[]` PDA of `["loan", loan state]`
+    ClaimCollateral,
+
+    /// Takes the collateral of an unfunded loan request back, closing it
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` Borrower's account
+    /// 1. `[writable]` Borrower's X Token Account
+    /// 2. `[writable]` Loan State Account
+    /// 3. `[writable]` Collateral Vault
+    /// 4. `[]` Token Program
+    /// 5. `[]` PDA of `["loan", loan state]`
+    CancelLoanRequest,
 }
 
 impl EscrowInstruction {
@@ -54,12 +925,29 @@ impl EscrowInstruction {
         let expected_y_amount = data
             .get(0..8)
             .and_then(|slice| slice.try_into().ok())
-            .map(|parsed_data| u64::from_le_bytes(parsed_data))
+            .map(u64::from_le_bytes)
             .ok_or(ProgramError::InvalidInstructionData)?;
 
         Ok(expected_y_amount)
     }
 
+    fn unpack_hash_data(data: &[u8]) -> Result<[u8; 32], ProgramError> {
+        data.get(0..32)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(ProgramError::InvalidInstructionData)
+    }
+
+    fn unpack_escrow_options(data: &[u8]) -> Result<EscrowOptions, ProgramError> {
+        // Everything after the expected Y amount is optional
+        match data.get(8..) {
+            Some(options_data) if !options_data.is_empty() => {
+                EscrowOptions::try_from_slice(options_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)
+            }
+            _ => Ok(EscrowOptions::default()),
+        }
+    }
+
     pub fn unpack_instruction(instruction_data: &[u8]) -> Result<Self, ProgramError> {
         // Instruction -> TAG + DATA
         let (tag, rest_data) = instruction_data
@@ -70,12 +958,151 @@ impl EscrowInstruction {
             0 => Ok(Self::InitializeEscrow {
                 // Parse data and send
                 expected_y_token_amount: Self::unpack_token_data(rest_data)?,
+                options: Box::new(Self::unpack_escrow_options(rest_data)?),
             }),
             1 => Ok(Self::ExchangeEscrow {
                 // Parse data and send
                 expected_x_token_amount: Self::unpack_token_data(rest_data)?,
             }),
             2 => Ok(Self::CancelEscrow),
+            3 => Ok(Self::QuoteEscrowPrice),
+            4 => Ok(Self::PlaceBid {
+                bid_amount: Self::unpack_token_data(rest_data)?,
+            }),
+            5 => Ok(Self::SettleAuction),
+            6 => Ok(Self::CommitSealedBid {
+                commitment: Self::unpack_hash_data(rest_data)?,
+                deposit: Self::unpack_token_data(rest_data.get(32..).unwrap_or_default())?,
+            }),
+            7 => Ok(Self::RevealSealedBid {
+                bid_amount: Self::unpack_token_data(rest_data)?,
+                salt: Self::unpack_hash_data(rest_data.get(8..).unwrap_or_default())?,
+            }),
+            8 => Ok(Self::RefundSealedBid),
+            9 => Ok(Self::ClaimWithPreimage {
+                preimage: rest_data.to_vec(),
+            }),
+            10 => Ok(Self::ExchangeSignedOrder),
+            11 => Ok(Self::IncrementMakerNonce),
+            12 => {
+                let (final_deadline, milestones) =
+                    <(i64, Vec<Milestone>)>::try_from_slice(rest_data)
+                        .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Ok(Self::InitializeMilestoneEscrow {
+                    final_deadline,
+                    milestones,
+                })
+            }
+            13 => Ok(Self::ReleaseMilestone {
+                milestone_index: *rest_data
+                    .first()
+                    .ok_or(ProgramError::InvalidInstructionData)?,
+            }),
+            14 => Ok(Self::RefundMilestones),
+            15 => Ok(Self::RaiseDispute),
+            16 => Ok(Self::Resolve {
+                alice_basis_points: rest_data
+                    .get(0..2)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(u16::from_le_bytes)
+                    .ok_or(ProgramError::InvalidInstructionData)?,
+            }),
+            17 => Ok(Self::ReserveFiatTrade),
+            18 => Ok(Self::MarkFiatPaid),
+            19 => Ok(Self::ReleaseFiatTrade),
+            20 => Ok(Self::TimeoutFiatTrade),
+            21 => Ok(Self::InitializeVesting {
+                schedule: VestingSchedule::try_from_slice(rest_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            }),
+            22 => Ok(Self::WithdrawVested),
+            23 => Ok(Self::RevokeVesting),
+            24 => Ok(Self::InitializeStream {
+                terms: StreamTerms::try_from_slice(rest_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            }),
+            25 => Ok(Self::WithdrawStreamed),
+            26 => Ok(Self::CloseStream),
+            27 => Ok(Self::InitializeCampaign {
+                goal_amount: Self::unpack_token_data(rest_data)?,
+                deadline: Self::unpack_token_data(rest_data.get(8..).unwrap_or_default())? as i64,
+            }),
+            28 => Ok(Self::Contribute {
+                amount: Self::unpack_token_data(rest_data)?,
+            }),
+            29 => Ok(Self::ClaimCampaign),
+            30 => Ok(Self::RefundContribution),
+            31 => {
+                let (reward_amount, expiry_timestamp, approvers) =
+                    <(u64, i64, Vec<Pubkey>)>::try_from_slice(rest_data)
+                        .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Ok(Self::InitializeBounty {
+                    reward_amount,
+                    expiry_timestamp,
+                    approvers,
+                })
+            }
+            32 => Ok(Self::Award {
+                hunter_basis_points: Vec::<u16>::try_from_slice(rest_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            }),
+            33 => Ok(Self::ReclaimBounty),
+            34 => {
+                let (bid_amount, criteria) = <(u64, CollectionCriteria)>::try_from_slice(rest_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Ok(Self::InitializeCollectionBid {
+                    bid_amount,
+                    criteria,
+                })
+            }
+            35 => Ok(Self::FillCollectionBid {
+                merkle_proof: Vec::<[u8; 32]>::try_from_slice(rest_data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            }),
+            36 => Ok(Self::CancelCollectionBid),
+            37 => {
+                let (x_token_amount, y_token_amount, taker_pubkey) =
+                    <(u64, u64, Pubkey)>::try_from_slice(rest_data)
+                        .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Ok(Self::InitializeMutualEscrow {
+                    x_token_amount,
+                    y_token_amount,
+                    taker_pubkey,
+                })
+            }
+            38 => Ok(Self::DepositMutualEscrow),
+            39 => Ok(Self::WithdrawMutualDeposit),
+            40 => Ok(Self::SettleMutualEscrow),
+            41 => {
+                let (x_token_amount, premium_amount, strike_amount, expiry_timestamp) =
+                    <(u64, u64, u64, i64)>::try_from_slice(rest_data)
+                        .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Ok(Self::WriteCallOption {
+                    x_token_amount,
+                    premium_amount,
+                    strike_amount,
+                    expiry_timestamp,
+                })
+            }
+            42 => Ok(Self::BuyCallOption),
+            43 => Ok(Self::ExerciseCallOption),
+            44 => Ok(Self::ReclaimCallOption),
+            45 => Ok(Self::TransferEscrowOwnership),
+            46 => {
+                let (collateral_amount, principal_amount, interest_amount, loan_duration) =
+                    <(u64, u64, u64, i64)>::try_from_slice(rest_data)
+                        .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Ok(Self::RequestLoan {
+                    collateral_amount,
+                    principal_amount,
+                    interest_amount,
+                    loan_duration,
+                })
+            }
+            47 => Ok(Self::FundLoan),
+            48 => Ok(Self::RepayLoan),
+            49 => Ok(Self::ClaimCollateral),
+            50 => Ok(Self::CancelLoanRequest),
             _ => Err(ProgramError::InvalidInstructionData),
         }
     }
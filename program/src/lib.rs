@@ -1,4 +1,6 @@
+pub mod ed25519;
 pub mod instruction;
+pub mod metadata;
 pub mod processor;
 pub mod state;
 
@@ -0,0 +1,95 @@
+use borsh::BorshDeserialize;
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
+
+/// Token Metadata program (Metaplex)
+pub mod program {
+    solana_program::declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+}
+
+// Account key of version 1 metadata accounts
+const METADATA_V1_KEY: u8 = 4;
+
+#[derive(BorshDeserialize, Debug)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    /// Percentage of the royalties
+    pub share: u8,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+/// Leading fields of a Metaplex metadata account, only the ones this program cares about are read
+#[derive(BorshDeserialize, Debug)]
+pub struct Metadata {
+    pub key: u8,
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<u8>,
+    pub collection: Option<Collection>,
+}
+
+impl Metadata {
+    /// Whether the mint is a verified member of the collection
+    pub fn is_verified_in(&self, collection_pubkey: &Pubkey) -> bool {
+        self.collection
+            .as_ref()
+            .is_some_and(|collection| collection.verified && collection.key.eq(collection_pubkey))
+    }
+}
+
+/// Master edition PDA of a mint, which Metaplex leaves as the mint authority of its NFTs
+pub fn master_edition_address(mint_pubkey: &Pubkey) -> Pubkey {
+    let (master_edition_address, _) = Pubkey::find_program_address(
+        &[
+            b"metadata",
+            &program::id().to_bytes(),
+            &mint_pubkey.to_bytes(),
+            b"edition",
+        ],
+        &program::id(),
+    );
+
+    master_edition_address
+}
+
+/// Reads the metadata of a mint, making sure the passed account is the mint's metadata PDA
+pub fn metadata_of(
+    metadata_account: &AccountInfo,
+    mint_pubkey: &Pubkey,
+) -> Result<Metadata, ProgramError> {
+    let (metadata_address, _) = Pubkey::find_program_address(
+        &[
+            b"metadata",
+            &program::id().to_bytes(),
+            &mint_pubkey.to_bytes(),
+        ],
+        &program::id(),
+    );
+    if !metadata_address.eq(metadata_account.key) || !program::check_id(metadata_account.owner) {
+        msg!("Passed account is not the metadata account of the mint");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Metadata accounts are padded, so only the leading fields get read
+    let metadata = Metadata::deserialize(&mut &metadata_account.data.borrow()[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if metadata.key != METADATA_V1_KEY || !metadata.mint.eq(mint_pubkey) {
+        msg!("Passed metadata account is not a valid metadata account of the mint");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(metadata)
+}
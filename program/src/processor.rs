@@ -1,3 +1,5 @@
+use std::slice::Iter;
+
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -5,20 +7,39 @@ use solana_program::{
     msg,
     program::{invoke, invoke_signed},
     program_error::ProgramError,
+    program_option::COption,
     program_pack::{IsInitialized, Pack},
     pubkey::Pubkey,
-    sysvar::{rent::Rent, Sysvar},
+    system_instruction::{create_account, transfer as system_transfer},
+    sysvar::{clock::Clock, instructions::get_instruction_relative, rent::Rent, Sysvar},
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
 };
 use spl_token::{
     id as token_program_id,
     instruction::{
-        close_account as close_token_account, set_authority, transfer as token_transfer,
-        AuthorityType::AccountOwner as TokenAccountOwner,
+        burn, close_account as close_token_account, mint_to, set_authority,
+        transfer as token_transfer, AuthorityType::AccountOwner as TokenAccountOwner,
+        AuthorityType::MintTokens as MintTokensAuthority,
     },
-    state::Account as TokenState,
+    state::{Account as TokenState, Mint as MintState},
 };
 
-use crate::{instruction::EscrowInstruction, state::EscrowState};
+use crate::{
+    ed25519,
+    instruction::{EscrowInstruction, EscrowOptions},
+    metadata,
+    state::{
+        AccountType, Arbitration, BountyState, Bundle, CallOptionState, CampaignState,
+        CollectionBidState, CollectionCriteria, ContributionState, EnglishAuction, EscrowMode,
+        EscrowReceipt, EscrowState, FiatTrade, FiatTradeStatus, LoanState, MakerNonceState,
+        Milestone, MilestoneEscrowState, MutualEscrowState, NftListing, PriceDecay, RevenueSplit,
+        SealedBidAuction, SealedBidState, SignedOrder, StreamState, StreamTerms, VestingSchedule,
+        VestingState, XCustody, MAX_BOUNTY_APPROVERS, MAX_BOUNTY_HUNTERS, MAX_BUNDLE_LEGS,
+        MAX_MILESTONES, MAX_REVENUE_SPLIT_RECIPIENTS, TOTAL_BASIS_POINTS,
+    },
+};
 
 pub struct EscrowProcessor {}
 
@@ -35,9 +56,15 @@ impl EscrowProcessor {
         match parsed_instruction {
             EscrowInstruction::InitializeEscrow {
                 expected_y_token_amount,
+                options,
             } => {
                 msg!("Instruction: ESCROW INITIALIZE");
-                Self::process_initialize_escrow(program_id, accounts, expected_y_token_amount)
+                Self::process_initialize_escrow(
+                    program_id,
+                    accounts,
+                    expected_y_token_amount,
+                    *options,
+                )
             }
 
             EscrowInstruction::ExchangeEscrow {
@@ -51,6 +78,309 @@ impl EscrowProcessor {
                 msg!("Instruction: ESCROW CANCEL");
                 Self::process_cancel_escrow(program_id, accounts)
             }
+
+            EscrowInstruction::QuoteEscrowPrice => {
+                msg!("Instruction: ESCROW QUOTE PRICE");
+                Self::process_quote_escrow_price(program_id, accounts)
+            }
+
+            EscrowInstruction::PlaceBid { bid_amount } => {
+                msg!("Instruction: ESCROW PLACE BID");
+                Self::process_place_bid(program_id, accounts, bid_amount)
+            }
+
+            EscrowInstruction::SettleAuction => {
+                msg!("Instruction: ESCROW SETTLE AUCTION");
+                Self::process_settle_auction(program_id, accounts)
+            }
+
+            EscrowInstruction::CommitSealedBid {
+                commitment,
+                deposit,
+            } => {
+                msg!("Instruction: ESCROW COMMIT SEALED BID");
+                Self::process_commit_sealed_bid(program_id, accounts, commitment, deposit)
+            }
+
+            EscrowInstruction::RevealSealedBid { bid_amount, salt } => {
+                msg!("Instruction: ESCROW REVEAL SEALED BID");
+                Self::process_reveal_sealed_bid(program_id, accounts, bid_amount, salt)
+            }
+
+            EscrowInstruction::RefundSealedBid => {
+                msg!("Instruction: ESCROW REFUND SEALED BID");
+                Self::process_refund_sealed_bid(program_id, accounts)
+            }
+
+            EscrowInstruction::ClaimWithPreimage { preimage } => {
+                msg!("Instruction: ESCROW CLAIM WITH PREIMAGE");
+                Self::process_claim_with_preimage(program_id, accounts, &preimage)
+            }
+
+            EscrowInstruction::ExchangeSignedOrder => {
+                msg!("Instruction: ESCROW EXCHANGE SIGNED ORDER");
+                Self::process_exchange_signed_order(program_id, accounts)
+            }
+
+            EscrowInstruction::IncrementMakerNonce => {
+                msg!("Instruction: ESCROW INCREMENT MAKER NONCE");
+                Self::process_increment_maker_nonce(program_id, accounts)
+            }
+
+            EscrowInstruction::InitializeMilestoneEscrow {
+                final_deadline,
+                milestones,
+            } => {
+                msg!("Instruction: MILESTONE ESCROW INITIALIZE");
+                Self::process_initialize_milestone_escrow(
+                    program_id,
+                    accounts,
+                    final_deadline,
+                    milestones,
+                )
+            }
+
+            EscrowInstruction::ReleaseMilestone { milestone_index } => {
+                msg!("Instruction: MILESTONE ESCROW RELEASE");
+                Self::process_release_milestone(program_id, accounts, milestone_index)
+            }
+
+            EscrowInstruction::RefundMilestones => {
+                msg!("Instruction: MILESTONE ESCROW REFUND");
+                Self::process_refund_milestones(program_id, accounts)
+            }
+
+            EscrowInstruction::RaiseDispute => {
+                msg!("Instruction: ESCROW RAISE DISPUTE");
+                Self::process_raise_dispute(program_id, accounts)
+            }
+
+            EscrowInstruction::Resolve { alice_basis_points } => {
+                msg!("Instruction: ESCROW RESOLVE");
+                Self::process_resolve(program_id, accounts, alice_basis_points)
+            }
+
+            EscrowInstruction::ReserveFiatTrade => {
+                msg!("Instruction: ESCROW RESERVE FIAT TRADE");
+                Self::process_reserve_fiat_trade(program_id, accounts)
+            }
+
+            EscrowInstruction::MarkFiatPaid => {
+                msg!("Instruction: ESCROW MARK FIAT PAID");
+                Self::process_mark_fiat_paid(program_id, accounts)
+            }
+
+            EscrowInstruction::ReleaseFiatTrade => {
+                msg!("Instruction: ESCROW RELEASE FIAT TRADE");
+                Self::process_release_fiat_trade(program_id, accounts)
+            }
+
+            EscrowInstruction::TimeoutFiatTrade => {
+                msg!("Instruction: ESCROW TIMEOUT FIAT TRADE");
+                Self::process_timeout_fiat_trade(program_id, accounts)
+            }
+
+            EscrowInstruction::InitializeVesting { schedule } => {
+                msg!("Instruction: VESTING INITIALIZE");
+                Self::process_initialize_vesting(program_id, accounts, schedule)
+            }
+
+            EscrowInstruction::WithdrawVested => {
+                msg!("Instruction: VESTING WITHDRAW");
+                Self::process_withdraw_vested(program_id, accounts)
+            }
+
+            EscrowInstruction::RevokeVesting => {
+                msg!("Instruction: VESTING REVOKE");
+                Self::process_revoke_vesting(program_id, accounts)
+            }
+
+            EscrowInstruction::InitializeStream { terms } => {
+                msg!("Instruction: STREAM INITIALIZE");
+                Self::process_initialize_stream(program_id, accounts, terms)
+            }
+
+            EscrowInstruction::WithdrawStreamed => {
+                msg!("Instruction: STREAM WITHDRAW");
+                Self::process_withdraw_streamed(program_id, accounts)
+            }
+
+            EscrowInstruction::CloseStream => {
+                msg!("Instruction: STREAM CLOSE");
+                Self::process_close_stream(program_id, accounts)
+            }
+
+            EscrowInstruction::InitializeCampaign {
+                goal_amount,
+                deadline,
+            } => {
+                msg!("Instruction: CAMPAIGN INITIALIZE");
+                Self::process_initialize_campaign(program_id, accounts, goal_amount, deadline)
+            }
+
+            EscrowInstruction::Contribute { amount } => {
+                msg!("Instruction: CAMPAIGN CONTRIBUTE");
+                Self::process_contribute(program_id, accounts, amount)
+            }
+
+            EscrowInstruction::ClaimCampaign => {
+                msg!("Instruction: CAMPAIGN CLAIM");
+                Self::process_claim_campaign(program_id, accounts)
+            }
+
+            EscrowInstruction::RefundContribution => {
+                msg!("Instruction: CAMPAIGN REFUND");
+                Self::process_refund_contribution(program_id, accounts)
+            }
+
+            EscrowInstruction::InitializeBounty {
+                reward_amount,
+                expiry_timestamp,
+                approvers,
+            } => {
+                msg!("Instruction: BOUNTY INITIALIZE");
+                Self::process_initialize_bounty(
+                    program_id,
+                    accounts,
+                    reward_amount,
+                    expiry_timestamp,
+                    approvers,
+                )
+            }
+
+            EscrowInstruction::Award {
+                hunter_basis_points,
+            } => {
+                msg!("Instruction: BOUNTY AWARD");
+                Self::process_award(program_id, accounts, &hunter_basis_points)
+            }
+
+            EscrowInstruction::ReclaimBounty => {
+                msg!("Instruction: BOUNTY RECLAIM");
+                Self::process_reclaim_bounty(program_id, accounts)
+            }
+
+            EscrowInstruction::InitializeCollectionBid {
+                bid_amount,
+                criteria,
+            } => {
+                msg!("Instruction: COLLECTION BID INITIALIZE");
+                Self::process_initialize_collection_bid(program_id, accounts, bid_amount, criteria)
+            }
+
+            EscrowInstruction::FillCollectionBid { merkle_proof } => {
+                msg!("Instruction: COLLECTION BID FILL");
+                Self::process_fill_collection_bid(program_id, accounts, &merkle_proof)
+            }
+
+            EscrowInstruction::CancelCollectionBid => {
+                msg!("Instruction: COLLECTION BID CANCEL");
+                Self::process_cancel_collection_bid(program_id, accounts)
+            }
+
+            EscrowInstruction::InitializeMutualEscrow {
+                x_token_amount,
+                y_token_amount,
+                taker_pubkey,
+            } => {
+                msg!("Instruction: MUTUAL ESCROW INITIALIZE");
+                Self::process_initialize_mutual_escrow(
+                    program_id,
+                    accounts,
+                    x_token_amount,
+                    y_token_amount,
+                    taker_pubkey,
+                )
+            }
+
+            EscrowInstruction::DepositMutualEscrow => {
+                msg!("Instruction: MUTUAL ESCROW DEPOSIT");
+                Self::process_deposit_mutual_escrow(program_id, accounts)
+            }
+
+            EscrowInstruction::WithdrawMutualDeposit => {
+                msg!("Instruction: MUTUAL ESCROW WITHDRAW");
+                Self::process_withdraw_mutual_deposit(program_id, accounts)
+            }
+
+            EscrowInstruction::SettleMutualEscrow => {
+                msg!("Instruction: MUTUAL ESCROW SETTLE");
+                Self::process_settle_mutual_escrow(program_id, accounts)
+            }
+
+            EscrowInstruction::WriteCallOption {
+                x_token_amount,
+                premium_amount,
+                strike_amount,
+                expiry_timestamp,
+            } => {
+                msg!("Instruction: CALL OPTION WRITE");
+                Self::process_write_call_option(
+                    program_id,
+                    accounts,
+                    x_token_amount,
+                    premium_amount,
+                    strike_amount,
+                    expiry_timestamp,
+                )
+            }
+
+            EscrowInstruction::BuyCallOption => {
+                msg!("Instruction: CALL OPTION BUY");
+                Self::process_buy_call_option(program_id, accounts)
+            }
+
+            EscrowInstruction::ExerciseCallOption => {
+                msg!("Instruction: CALL OPTION EXERCISE");
+                Self::process_exercise_call_option(program_id, accounts)
+            }
+
+            EscrowInstruction::ReclaimCallOption => {
+                msg!("Instruction: CALL OPTION RECLAIM");
+                Self::process_reclaim_call_option(program_id, accounts)
+            }
+
+            EscrowInstruction::TransferEscrowOwnership => {
+                msg!("Instruction: ESCROW TRANSFER OWNERSHIP");
+                Self::process_transfer_escrow_ownership(program_id, accounts)
+            }
+
+            EscrowInstruction::RequestLoan {
+                collateral_amount,
+                principal_amount,
+                interest_amount,
+                loan_duration,
+            } => {
+                msg!("Instruction: LOAN REQUEST");
+                Self::process_request_loan(
+                    program_id,
+                    accounts,
+                    collateral_amount,
+                    principal_amount,
+                    interest_amount,
+                    loan_duration,
+                )
+            }
+
+            EscrowInstruction::FundLoan => {
+                msg!("Instruction: LOAN FUND");
+                Self::process_fund_loan(program_id, accounts)
+            }
+
+            EscrowInstruction::RepayLoan => {
+                msg!("Instruction: LOAN REPAY");
+                Self::process_repay_loan(program_id, accounts)
+            }
+
+            EscrowInstruction::ClaimCollateral => {
+                msg!("Instruction: LOAN CLAIM COLLATERAL");
+                Self::process_claim_collateral(program_id, accounts)
+            }
+
+            EscrowInstruction::CancelLoanRequest => {
+                msg!("Instruction: LOAN CANCEL");
+                Self::process_cancel_loan_request(program_id, accounts)
+            }
         }
     }
 
@@ -59,6 +389,7 @@ impl EscrowProcessor {
         program_id: &Pubkey,
         accounts: &[AccountInfo],
         expected_y_token_amount: u64,
+        options: EscrowOptions,
     ) -> ProgramResult {
         /* ALL ACCOUNTS */
         let accounts_iterable = &mut accounts.iter();
@@ -67,6 +398,7 @@ impl EscrowProcessor {
         // Alice temp X token account
         let alice_temp_x_token_account = next_account_info(accounts_iterable)?;
         // Alice Y token account (to store it in the state such that Bob's transaction knows where to send his)
+        // This can also be a beneficiary's Y token account, in which case the proceeds go to the beneficiary
         let alice_y_token_account = next_account_info(accounts_iterable)?;
 
         // Escrow state account (Created before transaction and this account's owner would be our program)
@@ -75,6 +407,11 @@ impl EscrowProcessor {
         let rent = Rent::get()?;
         // Token Program ID
         let token_program = next_account_info(accounts_iterable)?;
+        // Rent refund recipient (optional, Alice gets the rent back if not passed)
+        let rent_refund_pubkey = match next_account_info(accounts_iterable) {
+            Ok(rent_refund_account) => *rent_refund_account.key,
+            Err(_) => *alice_account.key,
+        };
 
         /* LOGIC STARTS */
         // Checking if Alice has signed the transaction
@@ -84,13 +421,14 @@ impl EscrowProcessor {
         }
 
         // Checking if the passed account is actually a token account and not a mint account
-        match TokenState::unpack(&alice_y_token_account.data.borrow()) {
-            Ok(val) => val,
-            Err(err) => {
-                msg!("Escrow Initialize: Account passed is not a token account");
-                return Err(err);
-            }
-        };
+        let alice_y_token_account_state =
+            match TokenState::unpack(&alice_y_token_account.data.borrow()) {
+                Ok(val) => val,
+                Err(err) => {
+                    msg!("Escrow Initialize: Account passed is not a token account");
+                    return Err(err);
+                }
+            };
 
         // Checking if the passed token account's owner is the token program
         if !spl_token::check_id(alice_y_token_account.owner) {
@@ -108,7 +446,7 @@ impl EscrowProcessor {
 
         // Checking if the escrow state has already been initialized (only empty accounts can be initialized)
         let mut escrow_account_state =
-            EscrowState::try_from_slice(&escrow_account.data.borrow()[..])?;
+            EscrowState::unpack_from_slice(&escrow_account.data.borrow()[..])?;
 
         if escrow_account_state.is_initialized() {
             msg!("Escrow Initialize: Account already initialized");
@@ -131,28 +469,280 @@ impl EscrowProcessor {
         escrow_account_state.alice_temp_x_token_pubkey = *alice_temp_x_token_account.key;
         escrow_account_state.alice_y_token_pubkey = *alice_y_token_account.key;
         escrow_account_state.escrow_pda_bump = bump_seed;
+        escrow_account_state.y_beneficiary_pubkey = alice_y_token_account_state.owner;
+        escrow_account_state.rent_refund_pubkey = rent_refund_pubkey;
+
+        // Checking if the revenue split table (if any) is valid
+        if !options.revenue_splits.is_empty() {
+            Self::validate_revenue_splits(&options.revenue_splits, options.dust_recipient_index)?;
+        }
+        escrow_account_state.revenue_splits = options.revenue_splits;
+        escrow_account_state.dust_recipient_index = options.dust_recipient_index;
+
+        // Checking if the escrow mode parameters are valid
+        Self::validate_escrow_mode(&options.mode)?;
+        escrow_account_state.mode = options.mode;
+
+        // Delegated escrows leave X in Alice's X token account until the fill
+        if let XCustody::Delegate { x_token_amount } = options.custody {
+            if !matches!(
+                escrow_account_state.mode,
+                EscrowMode::FixedPrice | EscrowMode::DutchAuction(_)
+            ) {
+                msg!("Escrow Initialize: Only fixed price escrows and Dutch auctions can be delegated");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            if x_token_amount == 0 {
+                msg!("Escrow Initialize: Delegated X token amount has to be positive");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            Self::validate_x_delegation(
+                alice_temp_x_token_account,
+                alice_account.key,
+                &escrow_program_derived_address,
+                x_token_amount,
+            )?;
+        }
+        escrow_account_state.custody = options.custody;
+
+        // Checking if the arbitration (if any) is valid, P2P fiat trades escalate to the arbiter so they need one
+        let is_fiat_trade = matches!(escrow_account_state.mode, EscrowMode::FiatTrade(_));
+        if let Some(arbitration) = &options.arbitration {
+            if !matches!(
+                escrow_account_state.mode,
+                EscrowMode::FixedPrice | EscrowMode::DutchAuction(_) | EscrowMode::FiatTrade(_)
+            ) || escrow_account_state.custody != XCustody::Vault
+            {
+                msg!("Escrow Initialize: Only fixed price escrows, Dutch auctions and fiat trades holding X can have an arbiter");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            Self::validate_arbitration(arbitration, alice_account.key, is_fiat_trade)?;
+        } else if is_fiat_trade {
+            msg!("Escrow Initialize: Fiat trades need an arbiter");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        escrow_account_state.arbitration = options.arbitration.map(|arbitration| Arbitration {
+            is_disputed: false,
+            ..arbitration
+        });
+
+        // Auctions need a Y vault owned by the PDA to hold the bids, fiat trades start open
+        let bid_vault_account = match &mut escrow_account_state.mode {
+            EscrowMode::EnglishAuction(english_auction) => {
+                let bid_vault_account = next_account_info(accounts_iterable)?;
+                Self::validate_empty_vault(
+                    bid_vault_account,
+                    alice_account.key,
+                    &alice_y_token_account_state.mint,
+                )?;
+
+                english_auction.bid_vault_pubkey = *bid_vault_account.key;
+                english_auction.highest_bid = 0;
+                english_auction.highest_bidder_pubkey = Pubkey::default();
+                Some(bid_vault_account)
+            }
+            EscrowMode::SealedBidAuction(sealed_bid_auction) => {
+                let bid_vault_account = next_account_info(accounts_iterable)?;
+                Self::validate_empty_vault(
+                    bid_vault_account,
+                    alice_account.key,
+                    &alice_y_token_account_state.mint,
+                )?;
+
+                sealed_bid_auction.bid_vault_pubkey = *bid_vault_account.key;
+                sealed_bid_auction.open_bids = 0;
+                sealed_bid_auction.highest_bid = 0;
+                sealed_bid_auction.highest_bidder_pubkey = Pubkey::default();
+                sealed_bid_auction.is_settled = false;
+                Some(bid_vault_account)
+            }
+            EscrowMode::FiatTrade(fiat_trade) => {
+                fiat_trade.status = FiatTradeStatus::Open;
+                fiat_trade.buyer_pubkey = Pubkey::default();
+                fiat_trade.step_deadline = 0;
+                None
+            }
+            _ => None,
+        };
+
+        // Bundles hand every extra X vault over to the PDA as well
+        let mut bundle_x_vault_accounts = Vec::new();
+        if let EscrowMode::Bundle(bundle) = &escrow_account_state.mode {
+            for x_leg in bundle.x_legs.iter() {
+                let x_vault_account = next_account_info(accounts_iterable)?;
+                if !x_leg.token_pubkey.eq(x_vault_account.key) {
+                    msg!("Escrow Initialize: Passed X Vault mismatch with the bundle's X leg");
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                let x_vault_state = TokenState::unpack(&x_vault_account.data.borrow())?;
+                if !x_vault_state.owner.eq(alice_account.key)
+                    || x_vault_state.amount != x_leg.amount
+                {
+                    msg!("Escrow Initialize: X Vaults have to be Alice's and hold their leg's amount");
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                bundle_x_vault_accounts.push(x_vault_account);
+            }
+
+            for y_leg in bundle.y_legs.iter() {
+                let y_token_account = next_account_info(accounts_iterable)?;
+                if !y_leg.token_pubkey.eq(y_token_account.key)
+                    || !spl_token::check_id(y_token_account.owner)
+                {
+                    msg!("Escrow Initialize: Passed Y Token Account mismatch with the bundle's Y leg");
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                TokenState::unpack(&y_token_account.data.borrow())?;
+            }
+        }
+
+        // NFT listings have to escrow the single unit of the NFT mint
+        if let Some(nft) = &options.nft {
+            // Royalties are paid on exchange, so NFTs can't be sold through any other way
+            if !matches!(
+                escrow_account_state.mode,
+                EscrowMode::FixedPrice | EscrowMode::DutchAuction(_) | EscrowMode::Bundle(_)
+            ) || escrow_account_state.arbitration.is_some()
+            {
+                msg!("Escrow Initialize: NFTs can only be listed at a fixed price, through a Dutch auction or in a bundle, without arbiter");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            let nft_mint_account = next_account_info(accounts_iterable)?;
+            if !nft_mint_account.key.eq(&nft.mint_pubkey) {
+                msg!("Escrow Initialize: Passed NFT Mint mismatch with the listing's");
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            Self::validate_nft_mint(nft_mint_account)?;
+
+            let x_token_account_state =
+                TokenState::unpack(&alice_temp_x_token_account.data.borrow())?;
+            let x_token_amount = match escrow_account_state.custody {
+                XCustody::Vault => x_token_account_state.amount,
+                XCustody::Delegate { x_token_amount } => x_token_amount,
+            };
+            if !x_token_account_state.mint.eq(&nft.mint_pubkey) || x_token_amount != 1 {
+                msg!("Escrow Initialize: X has to be the single unit of the NFT");
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let nft_metadata_account = next_account_info(accounts_iterable)?;
+            if !nft_metadata_account.key.eq(&nft.metadata_pubkey) {
+                msg!("Escrow Initialize: Passed NFT Metadata Account mismatch with the listing's");
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            metadata::metadata_of(nft_metadata_account, &nft.mint_pubkey)?;
+        }
+        escrow_account_state.nft = options.nft;
+
+        // Receipt escrows are owned by whoever holds the receipt, so only the plain ones paying a single Y token account qualify
+        let receipt_accounts = match options.receipt_mint {
+            Some(receipt_mint_pubkey) => {
+                if !matches!(
+                    escrow_account_state.mode,
+                    EscrowMode::FixedPrice | EscrowMode::DutchAuction(_)
+                ) || escrow_account_state.custody != XCustody::Vault
+                    || escrow_account_state.arbitration.is_some()
+                    || !escrow_account_state.revenue_splits.is_empty()
+                {
+                    msg!("Escrow Initialize: Only fixed price escrows and Dutch auctions holding X without splits or arbiter can have a receipt");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                let receipt_mint_account = next_account_info(accounts_iterable)?;
+                let alice_receipt_token_account = next_account_info(accounts_iterable)?;
+                if !receipt_mint_account.key.eq(&receipt_mint_pubkey) {
+                    msg!("Escrow Initialize: Passed Receipt Mint mismatch with the options'");
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                Self::validate_empty_mint(receipt_mint_account, alice_account.key)?;
+                escrow_account_state.receipt = Some(EscrowReceipt {
+                    mint_pubkey: receipt_mint_pubkey,
+                    y_token_mint_pubkey: alice_y_token_account_state.mint,
+                });
+                Some((receipt_mint_account, alice_receipt_token_account))
+            }
+            None => {
+                escrow_account_state.receipt = None;
+                None
+            }
+        };
+
+        // The state is variable sized, so the escrow state account grows to fit it (Alice pays the extra rent)
+        let escrow_account_state_size = escrow_account_state.try_to_vec()?.len();
+        if escrow_account_state_size > escrow_account_storage_size {
+            let system_program = next_account_info(accounts_iterable)?;
+            Self::grow_program_account(
+                alice_account,
+                escrow_account,
+                system_program,
+                escrow_account_state_size,
+            )?;
+        }
 
         escrow_account_state.serialize(&mut (&mut escrow_account.data.borrow_mut()[..]))?;
 
+        if let XCustody::Delegate { .. } = escrow_account_state.custody {
+            return Ok(());
+        }
+
         // Cross Program Invocation (Token account ownership transfer to PDA)
         msg!("Transferring Alice temp X tokens to Escrow PDA");
-        let transfer_temp_token_owner_to_pda_instruction = set_authority(
-            token_program.key,
-            alice_temp_x_token_account.key,
-            Some(&escrow_program_derived_address),
-            TokenAccountOwner,
-            alice_account.key,
-            &[&alice_account.key],
+        Self::transfer_token_account_ownership(
+            token_program,
+            alice_temp_x_token_account,
+            alice_account,
+            &escrow_program_derived_address,
         )?;
 
-        invoke(
-            &transfer_temp_token_owner_to_pda_instruction,
-            &[
-                alice_temp_x_token_account.clone(),
-                alice_account.clone(),
-                token_program.clone(),
-            ],
-        )?;
+        if let Some(bid_vault_account) = bid_vault_account {
+            msg!("Transferring Bid Vault ownership to Escrow PDA");
+            Self::transfer_token_account_ownership(
+                token_program,
+                bid_vault_account,
+                alice_account,
+                &escrow_program_derived_address,
+            )?;
+        }
+
+        for x_vault_account in bundle_x_vault_accounts {
+            msg!("Transferring X Vault ownership to Escrow PDA");
+            Self::transfer_token_account_ownership(
+                token_program,
+                x_vault_account,
+                alice_account,
+                &escrow_program_derived_address,
+            )?;
+        }
+
+        if let Some((receipt_mint_account, alice_receipt_token_account)) = receipt_accounts {
+            msg!("Minting the receipt to Alice");
+            Self::mint_tokens(
+                token_program,
+                receipt_mint_account,
+                alice_receipt_token_account,
+                alice_account,
+                &[],
+                1,
+            )?;
+
+            msg!("Revoking the Receipt Mint authority");
+            Self::transfer_mint_authority(
+                token_program,
+                receipt_mint_account,
+                alice_account,
+                None,
+            )?;
+        }
 
         Ok(())
     }
@@ -170,22 +760,28 @@ impl EscrowProcessor {
         let bob_y_token_account = next_account_info(mutable_accounts)?;
         let bob_x_token_account = next_account_info(mutable_accounts)?;
         let pda_temp_x_token_account = next_account_info(mutable_accounts)?;
-        let alice_account = next_account_info(mutable_accounts)?;
+        let rent_refund_account = next_account_info(mutable_accounts)?;
         let alice_y_token_account = next_account_info(mutable_accounts)?;
         let escrow_account = next_account_info(mutable_accounts)?;
         let token_program = next_account_info(mutable_accounts)?;
         let escrow_program_pda = next_account_info(mutable_accounts)?;
 
+        // Checking if passed escrow state account is owned by this program
+        if !escrow_account.owner.eq(program_id) {
+            msg!("Escrow Exchange: Incorrect Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
         // All Account States
         let pda_temp_x_token_account_state =
             TokenState::unpack(&pda_temp_x_token_account.data.borrow())?;
 
-        let escrow_account_state = EscrowState::try_from_slice(&escrow_account.data.borrow())?;
+        let escrow_account_state = EscrowState::unpack_from_slice(&escrow_account.data.borrow())?;
 
         // All PDAs
         let pda_seed_bump_combination: &[&[u8]] = &[
             b"escrow",
-            &alice_account.key.to_bytes(),
+            &escrow_account_state.alice_pubkey.to_bytes(),
             &[escrow_account_state.escrow_pda_bump],
         ];
 
@@ -202,21 +798,49 @@ impl EscrowProcessor {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        // Checking if the escrow's alice address is the same as passed address
-        if !escrow_account_state.alice_pubkey.eq(alice_account.key) {
-            msg!("Escrow Exchange: Passed Alice's address mismatch with Escrow state's alice address");
-            return Err(ProgramError::InvalidAccountData);
+        // Escrows with an arbiter can only be filled by the counterparty, and not while disputed
+        if let Some(arbitration) = &escrow_account_state.arbitration {
+            if !arbitration.counterparty_pubkey.eq(bob_account.key) {
+                msg!("Escrow Exchange: Caller is not the escrow's counterparty");
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            if arbitration.is_disputed {
+                msg!("Escrow Exchange: Escrow is disputed");
+                return Err(ProgramError::InvalidAccountData);
+            }
         }
 
-        // Checking if escrow's alice y token address is the same as passed y token address
+        // Checking if the escrow's rent refund address is the same as passed address
         if !escrow_account_state
-            .alice_y_token_pubkey
-            .eq(alice_y_token_account.key)
+            .rent_refund_pubkey
+            .eq(rent_refund_account.key)
+        {
+            msg!("Escrow Exchange: Passed rent refund address mismatch with Escrow state's rent refund address");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if escrow's alice y token address is the same as passed y token address (receipt escrows pay the receipt holder's instead)
+        if escrow_account_state.receipt.is_none()
+            && !escrow_account_state
+                .alice_y_token_pubkey
+                .eq(alice_y_token_account.key)
         {
             msg!("Escrow Exchange: Passed Alice's Y Token address mismatch with Escrow state's Alice Y Token address");
             return Err(ProgramError::InvalidAccountData);
         }
 
+        // Checking if the Y token account still belongs to the beneficiary recorded at initialization
+        let alice_y_token_account_state = TokenState::unpack(&alice_y_token_account.data.borrow())?;
+        if escrow_account_state.receipt.is_none() {
+            let bob_y_token_account_state = TokenState::unpack(&bob_y_token_account.data.borrow())?;
+            Self::validate_y_beneficiary_account(
+                &escrow_account_state,
+                alice_y_token_account,
+                &bob_y_token_account_state.mint,
+            )?;
+        }
+
         // Checking if correct PDA is passed
         if !checking_pda.eq(escrow_program_pda.key) {
             msg!("Escrow Exchange: Incorrect PDA passed");
@@ -238,42 +862,152 @@ impl EscrowProcessor {
             return Err(ProgramError::InvalidAccountData);
         }
 
+        let x_token_amount = match escrow_account_state.custody {
+            XCustody::Vault => pda_temp_x_token_account_state.amount,
+            XCustody::Delegate { x_token_amount } => {
+                // Alice may have revoked the delegation or spent the X tokens since
+                Self::validate_x_delegation(
+                    pda_temp_x_token_account,
+                    &escrow_account_state.alice_pubkey,
+                    escrow_program_pda.key,
+                    x_token_amount,
+                )?;
+                x_token_amount
+            }
+        };
+
+        // NFT listings can only ever hand over the single unit of the NFT mint
+        if let Some(nft) = &escrow_account_state.nft {
+            if !pda_temp_x_token_account_state.mint.eq(&nft.mint_pubkey) || x_token_amount != 1 {
+                msg!("Escrow Exchange: Escrow no longer holds the single unit of the NFT");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
         // Checking if pda's temp token account's amount is equal to what bob asked
-        if expected_x_token_amount != pda_temp_x_token_account_state.amount {
+        if expected_x_token_amount != x_token_amount {
             msg!("Escrow Exchange: Bob's expected x token amount mismatch with pda token account balance");
             return Err(ProgramError::InsufficientFunds);
         }
 
-        // Transferring Y Tokens from Bob's Y Token Account to Alice's Y Token Account
-        let transfer_y_tokens_to_alice_ix = token_transfer(
-            &token_program_id(),
-            &bob_y_token_account.key,
-            &alice_y_token_account.key,
-            &bob_account.key,
-            &[&bob_account.key],
-            escrow_account_state.expected_y_token_amount,
+        // Making sure Bob's X token account can receive the X tokens (creating his associated token account if needed)
+        Self::prepare_bob_x_token_account(
+            bob_account,
+            bob_x_token_account,
+            &pda_temp_x_token_account_state.mint,
+            token_program,
+            mutable_accounts,
         )?;
 
-        msg!("Transferring Y Tokens from Bob's Y Token Account to Alice's Y Token Account");
+        // Receipt escrows pay the current receipt holder, in the Y mint recorded at initialization
+        if let Some(receipt) = &escrow_account_state.receipt {
+            let receipt_token_account = next_account_info(mutable_accounts)?;
+            Self::validate_receipt_holder(
+                receipt_token_account,
+                &receipt.mint_pubkey,
+                &alice_y_token_account_state.owner,
+            )?;
 
-        invoke(
-            &transfer_y_tokens_to_alice_ix,
-            &[
-                bob_y_token_account.clone(),
-                alice_y_token_account.clone(),
-                bob_account.clone(),
-                token_program.clone(),
-            ],
+            if !alice_y_token_account_state
+                .mint
+                .eq(&receipt.y_token_mint_pubkey)
+            {
+                msg!("Escrow Exchange: Receipt holder's Y Token Account mint mismatch with Escrow state's");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        // English and sealed-bid auctions are only filled through bids
+        if matches!(
+            escrow_account_state.mode,
+            EscrowMode::EnglishAuction(_) | EscrowMode::SealedBidAuction(_)
+        ) {
+            msg!("Escrow Exchange: Auctions are settled with the highest bid");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Hash time locked escrows are only released against the preimage
+        if let EscrowMode::HashTimeLock(_) = escrow_account_state.mode {
+            msg!("Escrow Exchange: Hash time locked escrows are claimed with the preimage");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Fiat trades are paid off-chain and released by Alice
+        if let EscrowMode::FiatTrade(_) = escrow_account_state.mode {
+            msg!("Escrow Exchange: Fiat trades are released by Alice");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Current Y price (only changes over time for Dutch auctions)
+        let clock = Clock::get()?;
+        let y_token_amount = escrow_account_state.current_y_price(clock.unix_timestamp)?;
+        msg!("Escrow Exchange: Y price is {}", y_token_amount);
+
+        // Creators of NFTs get their royalties out of the Y leg before Alice is paid
+        let y_token_amount = match &escrow_account_state.nft {
+            Some(nft) => Self::pay_nft_royalties(
+                nft,
+                y_token_amount,
+                &alice_y_token_account_state.mint,
+                bob_y_token_account,
+                bob_account,
+                token_program,
+                mutable_accounts,
+            )?,
+            None => y_token_amount,
+        };
+
+        // Transferring Y Tokens from Bob's Y Token Account to Alice's Y Token Account (or the revenue split recipients)
+        Self::transfer_y_proceeds(
+            &escrow_account_state,
+            y_token_amount,
+            bob_y_token_account,
+            bob_account,
+            &[],
+            alice_y_token_account,
+            token_program,
+            mutable_accounts,
         )?;
 
+        // Settling the extra legs of bundles, every one of them goes through or none does
+        if let EscrowMode::Bundle(bundle) = &escrow_account_state.mode {
+            for y_leg in bundle.y_legs.iter() {
+                let bob_token_account = next_account_info(mutable_accounts)?;
+                let alice_token_account = next_account_info(mutable_accounts)?;
+                if !y_leg.token_pubkey.eq(alice_token_account.key) {
+                    msg!("Escrow Exchange: Passed Alice's Token Account mismatch with the bundle's Y leg");
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                msg!("Transferring {} tokens of a Y leg to Alice", y_leg.amount);
+                Self::transfer_tokens(
+                    token_program,
+                    bob_token_account,
+                    alice_token_account,
+                    bob_account,
+                    &[],
+                    y_leg.amount,
+                )?;
+            }
+
+            Self::release_bundle_x_legs(
+                bundle,
+                escrow_program_pda,
+                pda_seed_bump_combination,
+                rent_refund_account,
+                token_program,
+                mutable_accounts,
+            )?;
+        }
+
         // Transferring tokens from PDA's Temp X token account to Bob's X token account
         let transfer_x_tokens_to_bob_ix = token_transfer(
             &token_program_id(),
-            &pda_temp_x_token_account.key,
-            &bob_x_token_account.key,
-            &escrow_program_pda.key,
-            &[&escrow_program_pda.key],
-            pda_temp_x_token_account_state.amount,
+            pda_temp_x_token_account.key,
+            bob_x_token_account.key,
+            escrow_program_pda.key,
+            &[escrow_program_pda.key],
+            x_token_amount,
         )?;
 
         msg!("Transferring X Tokens from PDA's Temp X Token Account to Bob's X Token Account");
@@ -291,13 +1025,19 @@ impl EscrowProcessor {
             &[pda_seed_bump_combination],
         )?;
 
+        // Alice's X token account stays hers when the X tokens were delegated
+        if let XCustody::Delegate { .. } = escrow_account_state.custody {
+            msg!("Closing Escrow State Account");
+            return Self::close_program_account(escrow_account, rent_refund_account);
+        }
+
         // Closing the PDA's Temp X Token account as there is no need to exist after transfer is complete
         let pda_temp_x_token_account_close_ix = close_token_account(
             &token_program_id(),
-            &pda_temp_x_token_account.key,
-            &alice_account.key,
-            &escrow_program_pda.key,
-            &[&escrow_program_pda.key],
+            pda_temp_x_token_account.key,
+            rent_refund_account.key,
+            escrow_program_pda.key,
+            &[escrow_program_pda.key],
         )?;
 
         msg!("Closing PDA's Temp X Token account");
@@ -306,25 +1046,15 @@ impl EscrowProcessor {
             &pda_temp_x_token_account_close_ix,
             &[
                 pda_temp_x_token_account.clone(),
-                alice_account.clone(),
+                rent_refund_account.clone(),
                 escrow_program_pda.clone(),
                 token_program.clone(),
             ],
             &[pda_seed_bump_combination],
         )?;
 
-        // Adding lamports to alice's account and decreasing from escrow state account;
         msg!("Closing Escrow State Account");
-        let alice_account_balance = &mut alice_account.lamports();
-        let escrow_account_balance = escrow_account.lamports();
-        **alice_account.lamports.borrow_mut() = alice_account_balance
-            .checked_add(escrow_account_balance)
-            .ok_or(ProgramError::InsufficientFunds)?;
-
-        **escrow_account.lamports.borrow_mut() = 0;
-        *escrow_account.data.borrow_mut() = &mut [];
-
-        Ok(())
+        Self::close_program_account(escrow_account, rent_refund_account)
     }
 
     // Cancel escrow processor
@@ -337,6 +1067,14 @@ impl EscrowProcessor {
         let alice_x_token_account = next_account_info(accounts_iterable)?;
         let token_program = next_account_info(accounts_iterable)?;
         let escrow_pda_account = next_account_info(accounts_iterable)?;
+        // Rent refund recipient (optional when the rent goes back to Alice)
+        let rent_refund_account = next_account_info(accounts_iterable).unwrap_or(alice_account);
+
+        // Checking if Alice has signed the transaction
+        if !alice_account.is_signer {
+            msg!("Escrow Cancel: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
 
         // Checking if passed escrow state account is owned by this program
         if !escrow_account.owner.eq(program_id) {
@@ -345,20 +1083,39 @@ impl EscrowProcessor {
         }
 
         // All State unwrapping
-        let escrow_account_state = EscrowState::try_from_slice(&escrow_account.data.borrow())?;
+        let escrow_account_state = EscrowState::unpack_from_slice(&escrow_account.data.borrow())?;
 
-        // Checking if the escrow account's temp x token address matches with the passed x token account
+        // Checking if the caller is the escrow's alice (the receipt holder for receipt escrows)
+        if escrow_account_state.receipt.is_none()
+            && !escrow_account_state.alice_pubkey.eq(alice_account.key)
+        {
+            msg!(
+                "Escrow Cancel: Passed Alice's address mismatch with Escrow state's alice address"
+            );
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the escrow's rent refund address is the same as passed address
         if !escrow_account_state
-            .alice_temp_x_token_pubkey
-            .eq(pda_temporary_x_token_account.key)
+            .rent_refund_pubkey
+            .eq(rent_refund_account.key)
         {
-            msg!("Escrow Cancel: Passed Temporary X Token address mismatch with Escrow State's");
+            msg!("Escrow Cancel: Passed rent refund address mismatch with Escrow state's rent refund address");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the escrow account's temp x token address matches with the passed x token account
+        if !escrow_account_state
+            .alice_temp_x_token_pubkey
+            .eq(pda_temporary_x_token_account.key)
+        {
+            msg!("Escrow Cancel: Passed Temporary X Token address mismatch with Escrow State's");
             return Err(ProgramError::InvalidAccountData);
         }
 
         let pda_seed_bump_combination: &[&[u8]] = &[
             b"escrow",
-            &alice_account.key.to_bytes(),
+            &escrow_account_state.alice_pubkey.to_bytes(),
             &[escrow_account_state.escrow_pda_bump],
         ];
 
@@ -372,7 +1129,113 @@ impl EscrowProcessor {
             return Err(ProgramError::InvalidAccountData);
         }
 
+        // Receipt escrows are cancelled by the receipt holder, burning the receipt
+        if let Some(receipt) = &escrow_account_state.receipt {
+            let receipt_token_account = next_account_info(accounts_iterable)?;
+            let receipt_mint_account = next_account_info(accounts_iterable)?;
+            Self::validate_receipt_holder(
+                receipt_token_account,
+                &receipt.mint_pubkey,
+                alice_account.key,
+            )?;
+
+            msg!("Burning the receipt");
+            Self::burn_tokens(
+                token_program,
+                receipt_token_account,
+                receipt_mint_account,
+                alice_account,
+                1,
+            )?;
+        }
+
         // BUSINESS LOGIC STARTS
+        // Disputed escrows are left to the arbiter
+        if escrow_account_state.is_disputed() {
+            msg!("Escrow Cancel: Escrow is disputed");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Fiat trades can only be cancelled while no buyer is on it
+        if let EscrowMode::FiatTrade(fiat_trade) = &escrow_account_state.mode {
+            if fiat_trade.status != FiatTradeStatus::Open {
+                msg!("Escrow Cancel: Fiat trade has been reserved by a buyer");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        // Hash time locked escrows can only be refunded once the recipient could no longer claim
+        if let EscrowMode::HashTimeLock(hash_time_lock) = &escrow_account_state.mode {
+            let clock = Clock::get()?;
+            if clock.unix_timestamp < hash_time_lock.timelock {
+                msg!("Escrow Cancel: Timelock has not passed yet");
+                return Err(ProgramError::InvalidArgument);
+            }
+        }
+
+        // Auctions can only be cancelled before the first bid, closing the empty bid vault as well
+        let auction_bid_vault = match &escrow_account_state.mode {
+            EscrowMode::EnglishAuction(english_auction) => {
+                Some((english_auction.has_bids(), english_auction.bid_vault_pubkey))
+            }
+            EscrowMode::SealedBidAuction(sealed_bid_auction) => Some((
+                sealed_bid_auction.open_bids > 0,
+                sealed_bid_auction.bid_vault_pubkey,
+            )),
+            _ => None,
+        };
+
+        if let Some((has_bids, bid_vault_pubkey)) = auction_bid_vault {
+            if has_bids {
+                msg!("Escrow Cancel: Auction already has bids");
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let bid_vault_account = next_account_info(accounts_iterable)?;
+            let alice_y_token_account = next_account_info(accounts_iterable)?;
+            if !bid_vault_pubkey.eq(bid_vault_account.key) {
+                msg!("Escrow Cancel: Passed Bid Vault address mismatch with Escrow State's");
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            if !escrow_account_state
+                .alice_y_token_pubkey
+                .eq(alice_y_token_account.key)
+            {
+                msg!("Escrow Cancel: Passed Alice's Y Token address mismatch with Escrow state's Alice Y Token address");
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // Tokens sent to the bid vault without bidding go to Alice
+            msg!("Closing the bid vault");
+            Self::sweep_and_close_vault(
+                token_program,
+                bid_vault_account,
+                alice_y_token_account,
+                rent_refund_account,
+                escrow_pda_account,
+                &[pda_seed_bump_combination],
+            )?;
+        }
+
+        // Bundles give every extra X vault back as well
+        if let EscrowMode::Bundle(bundle) = &escrow_account_state.mode {
+            Self::release_bundle_x_legs(
+                bundle,
+                escrow_pda_account,
+                pda_seed_bump_combination,
+                rent_refund_account,
+                token_program,
+                accounts_iterable,
+            )?;
+        }
+
+        // Delegated X tokens never left Alice's X token account
+        if let XCustody::Delegate { .. } = escrow_account_state.custody {
+            msg!("Closing the escrow state account");
+            return Self::close_program_account(escrow_account, rent_refund_account);
+        }
+
         msg!("Transferring X Tokens back to Initializer X Token account");
 
         let temp_x_token_account_state =
@@ -386,10 +1249,10 @@ impl EscrowProcessor {
 
         let transfer_x_tokens_to_alice_ix = token_transfer(
             &token_program_id(),
-            &pda_temporary_x_token_account.key,
-            &alice_x_token_account.key,
-            &escrow_pda_account.key,
-            &[&escrow_pda_account.key],
+            pda_temporary_x_token_account.key,
+            alice_x_token_account.key,
+            escrow_pda_account.key,
+            &[escrow_pda_account.key],
             temp_x_token_account_state.amount,
         )?;
 
@@ -408,9 +1271,9 @@ impl EscrowProcessor {
 
         let close_temp_x_tokens_ix = close_token_account(
             &token_program_id(),
-            &pda_temporary_x_token_account.key,
-            &alice_account.key,
-            &escrow_pda_account.key,
+            pda_temporary_x_token_account.key,
+            rent_refund_account.key,
+            escrow_pda_account.key,
             &[escrow_pda_account.key],
         )?;
 
@@ -418,23 +1281,6427 @@ impl EscrowProcessor {
             &close_temp_x_tokens_ix,
             &[
                 pda_temporary_x_token_account.clone(),
-                alice_account.clone(),
+                rent_refund_account.clone(),
                 escrow_pda_account.clone(),
             ],
             &[pda_seed_bump_combination],
         )?;
 
         msg!("Closing the escrow state account");
+        Self::close_program_account(escrow_account, rent_refund_account)
+    }
 
-        let escrow_state_account_balance = escrow_account.lamports();
-        **alice_account.lamports.borrow_mut() = alice_account
-            .lamports()
-            .checked_add(escrow_state_account_balance)
-            .ok_or(ProgramError::InsufficientFunds)?;
+    // Quote escrow price processor
+    fn process_quote_escrow_price(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        let escrow_account = next_account_info(accounts_iterable)?;
+
+        // Checking if passed escrow state account is owned by this program
+        if !escrow_account.owner.eq(program_id) {
+            msg!("Escrow Quote: Incorrect Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let escrow_account_state = EscrowState::unpack_from_slice(&escrow_account.data.borrow())?;
+
+        if !escrow_account_state.is_initialized() {
+            msg!("Escrow Quote: Escrow State Account not initialized");
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        let clock = Clock::get()?;
+        let y_token_amount = escrow_account_state.current_y_price(clock.unix_timestamp)?;
+
+        msg!(
+            "ESCROW Y PRICE AT {}: {}",
+            clock.unix_timestamp,
+            y_token_amount
+        );
+
+        Ok(())
+    }
+
+    // Place bid processor
+    fn process_place_bid(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bid_amount: u64,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let bidder_account = next_account_info(accounts_iterable)?;
+        let bidder_y_token_account = next_account_info(accounts_iterable)?;
+        let escrow_account = next_account_info(accounts_iterable)?;
+        let bid_vault_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let escrow_pda_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the bidder has signed the transaction
+        if !bidder_account.is_signer {
+            msg!("Escrow Bid: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed escrow state account is owned by this program
+        if !escrow_account.owner.eq(program_id) {
+            msg!("Escrow Bid: Incorrect Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut escrow_account_state =
+            EscrowState::unpack_from_slice(&escrow_account.data.borrow())?;
+
+        let mut english_auction = match &escrow_account_state.mode {
+            EscrowMode::EnglishAuction(english_auction)
+                if escrow_account_state.is_initialized() =>
+            {
+                english_auction.clone()
+            }
+            _ => {
+                msg!("Escrow Bid: Escrow is not an English auction");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        };
+
+        // Checking if the passed bid vault is the escrow's
+        if !english_auction.bid_vault_pubkey.eq(bid_vault_account.key) {
+            msg!("Escrow Bid: Passed Bid Vault address mismatch with Escrow State's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let pda_seed_bump_combination: &[&[u8]] = &[
+            b"escrow",
+            &escrow_account_state.alice_pubkey.to_bytes(),
+            &[escrow_account_state.escrow_pda_bump],
+        ];
+
+        // Checking if the pda matches with the pda passed
+        let checking_pda_address =
+            Pubkey::create_program_address(pda_seed_bump_combination, program_id)?;
+        if !checking_pda_address.eq(escrow_pda_account.key) {
+            msg!("Escrow Bid: Escrow PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the auction is still running
+        let clock = Clock::get()?;
+        if clock.unix_timestamp >= english_auction.end_timestamp {
+            msg!("Escrow Bid: Auction has already ended");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Checking if the bid beats the highest bid
+        let min_next_bid = english_auction.min_next_bid()?;
+        if bid_amount < min_next_bid {
+            msg!("Escrow Bid: Bid has to be at least {}", min_next_bid);
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // BUSINESS LOGIC STARTS
+        msg!("Transferring the bid to the Bid Vault");
+        Self::transfer_tokens(
+            token_program,
+            bidder_y_token_account,
+            bid_vault_account,
+            bidder_account,
+            &[],
+            bid_amount,
+        )?;
+
+        // Refunding the previous highest bidder to their associated Y token account
+        if english_auction.has_bids() {
+            let previous_bidder_y_token_account = next_account_info(accounts_iterable)?;
+            let bid_vault_state = TokenState::unpack(&bid_vault_account.data.borrow())?;
+
+            Self::prepare_associated_token_account(
+                bidder_account,
+                &english_auction.highest_bidder_pubkey,
+                previous_bidder_y_token_account,
+                &bid_vault_state.mint,
+                token_program,
+                accounts_iterable,
+            )?;
+
+            msg!("Refunding the previous highest bid");
+            Self::transfer_tokens(
+                token_program,
+                bid_vault_account,
+                previous_bidder_y_token_account,
+                escrow_pda_account,
+                &[pda_seed_bump_combination],
+                english_auction.highest_bid,
+            )?;
+        }
+
+        english_auction.highest_bid = bid_amount;
+        english_auction.highest_bidder_pubkey = *bidder_account.key;
+        escrow_account_state.mode = EscrowMode::EnglishAuction(english_auction);
+
+        escrow_account_state.serialize(&mut (&mut escrow_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Settle auction processor
+    fn process_settle_auction(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let settler_account = next_account_info(accounts_iterable)?;
+        let escrow_account = next_account_info(accounts_iterable)?;
+        let pda_temp_x_token_account = next_account_info(accounts_iterable)?;
+        let bid_vault_account = next_account_info(accounts_iterable)?;
+        let x_token_destination_account = next_account_info(accounts_iterable)?;
+        let alice_y_token_account = next_account_info(accounts_iterable)?;
+        let rent_refund_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let escrow_pda_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the settler has signed the transaction
+        if !settler_account.is_signer {
+            msg!("Escrow Settle: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed escrow state account is owned by this program
+        if !escrow_account.owner.eq(program_id) {
+            msg!("Escrow Settle: Incorrect Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut escrow_account_state =
+            EscrowState::unpack_from_slice(&escrow_account.data.borrow())?;
+
+        // Both auction kinds end up with the highest bid sitting in the bid vault
+        let (auction_end_timestamp, bid_vault_pubkey, highest_bid, highest_bidder_pubkey) =
+            match &escrow_account_state.mode {
+                EscrowMode::EnglishAuction(english_auction)
+                    if escrow_account_state.is_initialized() =>
+                {
+                    (
+                        english_auction.end_timestamp,
+                        english_auction.bid_vault_pubkey,
+                        english_auction.highest_bid,
+                        english_auction.highest_bidder_pubkey,
+                    )
+                }
+                EscrowMode::SealedBidAuction(sealed_bid_auction)
+                    if escrow_account_state.is_initialized() && !sealed_bid_auction.is_settled =>
+                {
+                    (
+                        sealed_bid_auction.reveal_end_timestamp,
+                        sealed_bid_auction.bid_vault_pubkey,
+                        sealed_bid_auction.highest_bid,
+                        sealed_bid_auction.highest_bidder_pubkey,
+                    )
+                }
+                _ => {
+                    msg!("Escrow Settle: Escrow is not an auction waiting to be settled");
+                    return Err(ProgramError::InvalidAccountData);
+                }
+            };
+
+        // Checking if the passed vaults are the escrow's
+        if !escrow_account_state
+            .alice_temp_x_token_pubkey
+            .eq(pda_temp_x_token_account.key)
+        {
+            msg!("Escrow Settle: Passed Temporary X Token address mismatch with Escrow State's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !bid_vault_pubkey.eq(bid_vault_account.key) {
+            msg!("Escrow Settle: Passed Bid Vault address mismatch with Escrow State's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if escrow's alice y token address is the same as passed y token address (receipt escrows pay the receipt holder's instead)
+        if escrow_account_state.receipt.is_none()
+            && !escrow_account_state
+                .alice_y_token_pubkey
+                .eq(alice_y_token_account.key)
+        {
+            msg!("Escrow Settle: Passed Alice's Y Token address mismatch with Escrow state's Alice Y Token address");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the escrow's rent refund address is the same as passed address
+        if !escrow_account_state
+            .rent_refund_pubkey
+            .eq(rent_refund_account.key)
+        {
+            msg!("Escrow Settle: Passed rent refund address mismatch with Escrow state's rent refund address");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let pda_seed_bump_combination: &[&[u8]] = &[
+            b"escrow",
+            &escrow_account_state.alice_pubkey.to_bytes(),
+            &[escrow_account_state.escrow_pda_bump],
+        ];
+
+        // Checking if the pda matches with the pda passed
+        let checking_pda_address =
+            Pubkey::create_program_address(pda_seed_bump_combination, program_id)?;
+        if !checking_pda_address.eq(escrow_pda_account.key) {
+            msg!("Escrow Settle: Escrow PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the auction has ended
+        let clock = Clock::get()?;
+        if clock.unix_timestamp < auction_end_timestamp {
+            msg!("Escrow Settle: Auction is still running");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let pda_temp_x_token_account_state =
+            TokenState::unpack(&pda_temp_x_token_account.data.borrow())?;
+
+        // Checking if the Y token account still belongs to the beneficiary recorded at initialization
+        let bid_vault_state = TokenState::unpack(&bid_vault_account.data.borrow())?;
+        Self::validate_y_beneficiary_account(
+            &escrow_account_state,
+            alice_y_token_account,
+            &bid_vault_state.mint,
+        )?;
+
+        if highest_bid > 0 {
+            // X tokens go to the winner's associated token account
+            Self::prepare_associated_token_account(
+                settler_account,
+                &highest_bidder_pubkey,
+                x_token_destination_account,
+                &pda_temp_x_token_account_state.mint,
+                token_program,
+                accounts_iterable,
+            )?;
+
+            // Highest bid goes to Alice's Y token account (or the revenue split recipients)
+            Self::transfer_y_proceeds(
+                &escrow_account_state,
+                highest_bid,
+                bid_vault_account,
+                escrow_pda_account,
+                &[pda_seed_bump_combination],
+                alice_y_token_account,
+                token_program,
+                accounts_iterable,
+            )?;
+
+            msg!("Transferring X Tokens to the highest bidder");
+        } else {
+            // Without bids the X tokens go back to Alice
+            let x_token_destination_state =
+                TokenState::unpack(&x_token_destination_account.data.borrow())?;
+            if !x_token_destination_state
+                .owner
+                .eq(&escrow_account_state.alice_pubkey)
+                || !x_token_destination_state
+                    .mint
+                    .eq(&pda_temp_x_token_account_state.mint)
+            {
+                msg!("Escrow Settle: Passed X Token account is not Alice's");
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            msg!("Transferring X Tokens back to Alice as there were no bids");
+        }
+
+        Self::transfer_tokens(
+            token_program,
+            pda_temp_x_token_account,
+            x_token_destination_account,
+            escrow_pda_account,
+            &[pda_seed_bump_combination],
+            pda_temp_x_token_account_state.amount,
+        )?;
+
+        msg!("Closing the temporary X token account");
+        Self::close_vault(
+            token_program,
+            pda_temp_x_token_account,
+            rent_refund_account,
+            escrow_pda_account,
+            &[pda_seed_bump_combination],
+        )?;
+
+        // Sealed-bid deposits still have to be refunded, the escrow stays around until the last one is
+        if let EscrowMode::SealedBidAuction(sealed_bid_auction) = &mut escrow_account_state.mode {
+            sealed_bid_auction.is_settled = true;
+
+            if sealed_bid_auction.open_bids > 0 {
+                escrow_account_state.serialize(&mut (&mut escrow_account.data.borrow_mut()[..]))?;
+                return Ok(());
+            }
+        }
+
+        // Anything sent to the bid vault on top of the bids goes to Alice
+        msg!("Closing the bid vault");
+        Self::sweep_and_close_vault(
+            token_program,
+            bid_vault_account,
+            alice_y_token_account,
+            rent_refund_account,
+            escrow_pda_account,
+            &[pda_seed_bump_combination],
+        )?;
+
+        msg!("Closing the escrow state account");
+        Self::close_program_account(escrow_account, rent_refund_account)
+    }
+
+    // Commit sealed bid processor
+    fn process_commit_sealed_bid(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        commitment: [u8; 32],
+        deposit: u64,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let bidder_account = next_account_info(accounts_iterable)?;
+        let bidder_y_token_account = next_account_info(accounts_iterable)?;
+        let escrow_account = next_account_info(accounts_iterable)?;
+        let bid_vault_account = next_account_info(accounts_iterable)?;
+        let sealed_bid_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let system_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the bidder has signed the transaction
+        if !bidder_account.is_signer {
+            msg!("Escrow Commit: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed escrow state account is owned by this program
+        if !escrow_account.owner.eq(program_id) {
+            msg!("Escrow Commit: Incorrect Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut escrow_account_state =
+            EscrowState::unpack_from_slice(&escrow_account.data.borrow())?;
+
+        let mut sealed_bid_auction = match &escrow_account_state.mode {
+            EscrowMode::SealedBidAuction(sealed_bid_auction)
+                if escrow_account_state.is_initialized() =>
+            {
+                sealed_bid_auction.clone()
+            }
+            _ => {
+                msg!("Escrow Commit: Escrow is not a sealed-bid auction");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        };
+
+        // Checking if the passed bid vault is the escrow's
+        if !sealed_bid_auction
+            .bid_vault_pubkey
+            .eq(bid_vault_account.key)
+        {
+            msg!("Escrow Commit: Passed Bid Vault address mismatch with Escrow State's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the deposit is going to the escrow PDA's custody
+        let escrow_pda_address = Pubkey::create_program_address(
+            &[
+                b"escrow",
+                &escrow_account_state.alice_pubkey.to_bytes(),
+                &[escrow_account_state.escrow_pda_bump],
+            ],
+            program_id,
+        )?;
+        let bid_vault_state = TokenState::unpack(&bid_vault_account.data.borrow())?;
+        if !bid_vault_state.owner.eq(&escrow_pda_address) {
+            msg!("Escrow Commit: Bid Vault is not owned by the Escrow PDA");
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        // Checking if the commit window is still open
+        let clock = Clock::get()?;
+        if clock.unix_timestamp >= sealed_bid_auction.commit_end_timestamp {
+            msg!("Escrow Commit: Commit window has already ended");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Checking if the deposit covers at least the minimum bid
+        if deposit < sealed_bid_auction.min_bid {
+            msg!(
+                "Escrow Commit: Deposit has to be at least {}",
+                sealed_bid_auction.min_bid
+            );
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // Checking if the sealed bid account is the bidder's PDA for this escrow
+        let (sealed_bid_pubkey, sealed_bid_bump) = Pubkey::find_program_address(
+            &[
+                b"sealed_bid",
+                &escrow_account.key.to_bytes(),
+                &bidder_account.key.to_bytes(),
+            ],
+            program_id,
+        );
+        if !sealed_bid_pubkey.eq(sealed_bid_account.key) {
+            msg!("Escrow Commit: Sealed Bid Account mismatch");
+            return Err(ProgramError::InvalidSeeds);
+        }
 
-        **escrow_account.lamports.borrow_mut() = 0;
-        *escrow_account.data.borrow_mut() = &mut [];
+        // BUSINESS LOGIC STARTS
+        msg!("Creating the Sealed Bid Account");
+        Self::create_program_account(
+            program_id,
+            bidder_account,
+            sealed_bid_account,
+            system_program,
+            &[
+                b"sealed_bid",
+                &escrow_account.key.to_bytes(),
+                &bidder_account.key.to_bytes(),
+                &[sealed_bid_bump],
+            ],
+            SealedBidState::LEN,
+        )?;
+
+        msg!("Transferring the deposit to the Bid Vault");
+        Self::transfer_tokens(
+            token_program,
+            bidder_y_token_account,
+            bid_vault_account,
+            bidder_account,
+            &[],
+            deposit,
+        )?;
+
+        let sealed_bid_state = SealedBidState {
+            account_type: AccountType::SealedBid,
+            escrow_pubkey: *escrow_account.key,
+            bidder_pubkey: *bidder_account.key,
+            commitment,
+            deposit,
+            is_revealed: false,
+            revealed_amount: 0,
+            bump: sealed_bid_bump,
+        };
+        sealed_bid_state.serialize(&mut (&mut sealed_bid_account.data.borrow_mut()[..]))?;
+
+        sealed_bid_auction.open_bids = sealed_bid_auction
+            .open_bids
+            .checked_add(1)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        escrow_account_state.mode = EscrowMode::SealedBidAuction(sealed_bid_auction);
+
+        escrow_account_state.serialize(&mut (&mut escrow_account.data.borrow_mut()[..]))?;
 
         Ok(())
     }
+
+    // Reveal sealed bid processor
+    fn process_reveal_sealed_bid(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bid_amount: u64,
+        salt: [u8; 32],
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let bidder_account = next_account_info(accounts_iterable)?;
+        let escrow_account = next_account_info(accounts_iterable)?;
+        let sealed_bid_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the bidder has signed the transaction
+        if !bidder_account.is_signer {
+            msg!("Escrow Reveal: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed escrow state account is owned by this program
+        if !escrow_account.owner.eq(program_id) {
+            msg!("Escrow Reveal: Incorrect Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut escrow_account_state =
+            EscrowState::unpack_from_slice(&escrow_account.data.borrow())?;
+
+        let mut sealed_bid_auction = match &escrow_account_state.mode {
+            EscrowMode::SealedBidAuction(sealed_bid_auction)
+                if escrow_account_state.is_initialized() =>
+            {
+                sealed_bid_auction.clone()
+            }
+            _ => {
+                msg!("Escrow Reveal: Escrow is not a sealed-bid auction");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        };
+
+        let mut sealed_bid_state =
+            Self::unpack_sealed_bid_state(program_id, sealed_bid_account, escrow_account.key)?;
+
+        // Checking if the bidder is the one who committed the bid
+        if !sealed_bid_state.bidder_pubkey.eq(bidder_account.key) {
+            msg!("Escrow Reveal: Bidder mismatch with Sealed Bid State's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the reveal window is open
+        let clock = Clock::get()?;
+        if clock.unix_timestamp < sealed_bid_auction.commit_end_timestamp
+            || clock.unix_timestamp >= sealed_bid_auction.reveal_end_timestamp
+        {
+            msg!("Escrow Reveal: Reveal window is not open");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if sealed_bid_state.is_revealed {
+            msg!("Escrow Reveal: Bid has already been revealed");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the revealed bid matches the commitment
+        let commitment = SealedBidState::commitment_for(
+            escrow_account.key,
+            bidder_account.key,
+            bid_amount,
+            &salt,
+        );
+        if commitment != sealed_bid_state.commitment {
+            msg!("Escrow Reveal: Revealed bid does not match the commitment");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Checking if the revealed bid is within the minimum bid and the deposit
+        if bid_amount < sealed_bid_auction.min_bid || bid_amount > sealed_bid_state.deposit {
+            msg!(
+                "Escrow Reveal: Bid has to be between {} and the deposit of {}",
+                sealed_bid_auction.min_bid,
+                sealed_bid_state.deposit
+            );
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // BUSINESS LOGIC STARTS
+        sealed_bid_state.is_revealed = true;
+        sealed_bid_state.revealed_amount = bid_amount;
+        sealed_bid_state.serialize(&mut (&mut sealed_bid_account.data.borrow_mut()[..]))?;
+
+        // Ties go to the earliest reveal
+        if bid_amount > sealed_bid_auction.highest_bid {
+            msg!("Revealed bid is the highest so far");
+            sealed_bid_auction.highest_bid = bid_amount;
+            sealed_bid_auction.highest_bidder_pubkey = *bidder_account.key;
+            escrow_account_state.mode = EscrowMode::SealedBidAuction(sealed_bid_auction);
+
+            escrow_account_state.serialize(&mut (&mut escrow_account.data.borrow_mut()[..]))?;
+        }
+
+        Ok(())
+    }
+
+    // Refund sealed bid processor
+    fn process_refund_sealed_bid(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let caller_account = next_account_info(accounts_iterable)?;
+        let escrow_account = next_account_info(accounts_iterable)?;
+        let sealed_bid_account = next_account_info(accounts_iterable)?;
+        let bidder_account = next_account_info(accounts_iterable)?;
+        let bid_vault_account = next_account_info(accounts_iterable)?;
+        let bidder_y_token_account = next_account_info(accounts_iterable)?;
+        let alice_y_token_account = next_account_info(accounts_iterable)?;
+        let rent_refund_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let escrow_pda_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the caller has signed the transaction
+        if !caller_account.is_signer {
+            msg!("Escrow Refund: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed escrow state account is owned by this program
+        if !escrow_account.owner.eq(program_id) {
+            msg!("Escrow Refund: Incorrect Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut escrow_account_state =
+            EscrowState::unpack_from_slice(&escrow_account.data.borrow())?;
+
+        let mut sealed_bid_auction = match &escrow_account_state.mode {
+            EscrowMode::SealedBidAuction(sealed_bid_auction)
+                if escrow_account_state.is_initialized() && sealed_bid_auction.is_settled =>
+            {
+                sealed_bid_auction.clone()
+            }
+            _ => {
+                msg!("Escrow Refund: Escrow is not a settled sealed-bid auction");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        };
+
+        let sealed_bid_state =
+            Self::unpack_sealed_bid_state(program_id, sealed_bid_account, escrow_account.key)?;
+
+        // Checking if the passed accounts are the ones stored in the states
+        if !sealed_bid_state.bidder_pubkey.eq(bidder_account.key) {
+            msg!("Escrow Refund: Bidder mismatch with Sealed Bid State's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !sealed_bid_auction
+            .bid_vault_pubkey
+            .eq(bid_vault_account.key)
+        {
+            msg!("Escrow Refund: Passed Bid Vault address mismatch with Escrow State's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !escrow_account_state
+            .alice_y_token_pubkey
+            .eq(alice_y_token_account.key)
+        {
+            msg!("Escrow Refund: Passed Alice's Y Token address mismatch with Escrow state's Alice Y Token address");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !escrow_account_state
+            .rent_refund_pubkey
+            .eq(rent_refund_account.key)
+        {
+            msg!("Escrow Refund: Passed rent refund address mismatch with Escrow state's rent refund address");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let pda_seed_bump_combination: &[&[u8]] = &[
+            b"escrow",
+            &escrow_account_state.alice_pubkey.to_bytes(),
+            &[escrow_account_state.escrow_pda_bump],
+        ];
+
+        // Checking if the pda matches with the pda passed
+        let checking_pda_address =
+            Pubkey::create_program_address(pda_seed_bump_combination, program_id)?;
+        if !checking_pda_address.eq(escrow_pda_account.key) {
+            msg!("Escrow Refund: Escrow PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // BUSINESS LOGIC STARTS
+        // The winning bid has already been paid out of the winner's deposit while settling
+        let is_winner = sealed_bid_state.is_revealed
+            && sealed_bid_auction.highest_bid > 0
+            && sealed_bid_auction
+                .highest_bidder_pubkey
+                .eq(&sealed_bid_state.bidder_pubkey);
+        let refund_amount = if is_winner {
+            sealed_bid_state
+                .deposit
+                .checked_sub(sealed_bid_auction.highest_bid)
+                .ok_or(ProgramError::InsufficientFunds)?
+        } else {
+            sealed_bid_state.deposit
+        };
+
+        if refund_amount > 0 {
+            if !sealed_bid_state.is_revealed && sealed_bid_auction.forfeit_unrevealed_deposits {
+                let bid_vault_state = TokenState::unpack(&bid_vault_account.data.borrow())?;
+                Self::validate_y_beneficiary_account(
+                    &escrow_account_state,
+                    alice_y_token_account,
+                    &bid_vault_state.mint,
+                )?;
+
+                msg!("Forfeiting the unrevealed deposit to Alice");
+                Self::transfer_tokens(
+                    token_program,
+                    bid_vault_account,
+                    alice_y_token_account,
+                    escrow_pda_account,
+                    &[pda_seed_bump_combination],
+                    refund_amount,
+                )?;
+            } else {
+                let bid_vault_state = TokenState::unpack(&bid_vault_account.data.borrow())?;
+                if bidder_y_token_account.data_is_empty() {
+                    Self::create_associated_token_account_for(
+                        caller_account,
+                        bidder_account,
+                        bidder_y_token_account,
+                        &bid_vault_state.mint,
+                        token_program,
+                        accounts_iterable,
+                    )?;
+                } else if !get_associated_token_address(bidder_account.key, &bid_vault_state.mint)
+                    .eq(bidder_y_token_account.key)
+                {
+                    msg!("Escrow Refund: Passed Y Token account is not the bidder's associated token account");
+                    return Err(ProgramError::InvalidSeeds);
+                }
+
+                msg!("Refunding the deposit to the bidder");
+                Self::transfer_tokens(
+                    token_program,
+                    bid_vault_account,
+                    bidder_y_token_account,
+                    escrow_pda_account,
+                    &[pda_seed_bump_combination],
+                    refund_amount,
+                )?;
+            }
+        }
+
+        msg!("Closing the Sealed Bid Account");
+        Self::close_program_account(sealed_bid_account, bidder_account)?;
+
+        sealed_bid_auction.open_bids = sealed_bid_auction
+            .open_bids
+            .checked_sub(1)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        // The last refund closes whatever the auction still holds, tokens sent to the bid vault without bidding going to Alice
+        if sealed_bid_auction.open_bids == 0 {
+            msg!("Closing the bid vault");
+            Self::sweep_and_close_vault(
+                token_program,
+                bid_vault_account,
+                alice_y_token_account,
+                rent_refund_account,
+                escrow_pda_account,
+                &[pda_seed_bump_combination],
+            )?;
+
+            msg!("Closing the escrow state account");
+            return Self::close_program_account(escrow_account, rent_refund_account);
+        }
+
+        escrow_account_state.mode = EscrowMode::SealedBidAuction(sealed_bid_auction);
+        escrow_account_state.serialize(&mut (&mut escrow_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Claim with preimage processor
+    fn process_claim_with_preimage(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        preimage: &[u8],
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let claimer_account = next_account_info(accounts_iterable)?;
+        let escrow_account = next_account_info(accounts_iterable)?;
+        let pda_temp_x_token_account = next_account_info(accounts_iterable)?;
+        let recipient_x_token_account = next_account_info(accounts_iterable)?;
+        let rent_refund_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let escrow_pda_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the claimer has signed the transaction
+        if !claimer_account.is_signer {
+            msg!("Escrow Claim: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed escrow state account is owned by this program
+        if !escrow_account.owner.eq(program_id) {
+            msg!("Escrow Claim: Incorrect Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let escrow_account_state = EscrowState::unpack_from_slice(&escrow_account.data.borrow())?;
+
+        let hash_time_lock = match &escrow_account_state.mode {
+            EscrowMode::HashTimeLock(hash_time_lock) if escrow_account_state.is_initialized() => {
+                hash_time_lock
+            }
+            _ => {
+                msg!("Escrow Claim: Escrow is not hash time locked");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        };
+
+        // Checking if the escrow account's temp x token address matches with the passed x token account
+        if !escrow_account_state
+            .alice_temp_x_token_pubkey
+            .eq(pda_temp_x_token_account.key)
+        {
+            msg!("Escrow Claim: Passed Temporary X Token address mismatch with Escrow State's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the escrow's rent refund address is the same as passed address
+        if !escrow_account_state
+            .rent_refund_pubkey
+            .eq(rent_refund_account.key)
+        {
+            msg!("Escrow Claim: Passed rent refund address mismatch with Escrow state's rent refund address");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let pda_seed_bump_combination: &[&[u8]] = &[
+            b"escrow",
+            &escrow_account_state.alice_pubkey.to_bytes(),
+            &[escrow_account_state.escrow_pda_bump],
+        ];
+
+        // Checking if the pda matches with the pda passed
+        let checking_pda_address =
+            Pubkey::create_program_address(pda_seed_bump_combination, program_id)?;
+        if !checking_pda_address.eq(escrow_pda_account.key) {
+            msg!("Escrow Claim: Escrow PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the timelock has not passed yet
+        let clock = Clock::get()?;
+        if clock.unix_timestamp >= hash_time_lock.timelock {
+            msg!("Escrow Claim: Timelock has already passed");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Checking if the preimage unlocks the hashlock
+        if !hash_time_lock.is_unlocked_by(preimage) {
+            msg!("Escrow Claim: Preimage does not match the hashlock");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let preimage_hex: String = preimage
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        msg!("ESCROW HTLC PREIMAGE: {}", preimage_hex);
+
+        let pda_temp_x_token_account_state =
+            TokenState::unpack(&pda_temp_x_token_account.data.borrow())?;
+
+        Self::prepare_associated_token_account(
+            claimer_account,
+            &hash_time_lock.recipient_pubkey,
+            recipient_x_token_account,
+            &pda_temp_x_token_account_state.mint,
+            token_program,
+            accounts_iterable,
+        )?;
+
+        msg!("Transferring X Tokens to the recipient");
+        Self::transfer_tokens(
+            token_program,
+            pda_temp_x_token_account,
+            recipient_x_token_account,
+            escrow_pda_account,
+            &[pda_seed_bump_combination],
+            pda_temp_x_token_account_state.amount,
+        )?;
+
+        msg!("Closing the temporary X token account");
+        Self::close_vault(
+            token_program,
+            pda_temp_x_token_account,
+            rent_refund_account,
+            escrow_pda_account,
+            &[pda_seed_bump_combination],
+        )?;
+
+        msg!("Closing the escrow state account");
+        Self::close_program_account(escrow_account, rent_refund_account)
+    }
+
+    // Exchange signed order processor
+    fn process_exchange_signed_order(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let taker_account = next_account_info(accounts_iterable)?;
+        let taker_y_token_account = next_account_info(accounts_iterable)?;
+        let taker_x_token_account = next_account_info(accounts_iterable)?;
+        let maker_x_token_account = next_account_info(accounts_iterable)?;
+        let maker_y_token_account = next_account_info(accounts_iterable)?;
+        let maker_nonce_account = next_account_info(accounts_iterable)?;
+        let order_pda_account = next_account_info(accounts_iterable)?;
+        let instructions_sysvar = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let system_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the taker has signed the transaction
+        if !taker_account.is_signer {
+            msg!("Escrow Signed Order: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // The maker's signature is verified by the Ed25519 instruction right before this one
+        let ed25519_instruction = get_instruction_relative(-1, instructions_sysvar)?;
+        let (signer_pubkey, message) = ed25519::verified_message(&ed25519_instruction)?;
+
+        let signed_order = SignedOrder::try_from_slice(message)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        // Checking if the order is meant for this program and signed by its maker
+        if !signed_order.program_id.eq(program_id) {
+            msg!("Escrow Signed Order: Order is meant for another program");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if !signed_order.maker_pubkey.eq(&signer_pubkey) {
+            msg!("Escrow Signed Order: Order is not signed by its maker");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if the passed maker accounts are the order's
+        if !signed_order
+            .maker_x_token_pubkey
+            .eq(maker_x_token_account.key)
+            || !signed_order
+                .maker_y_token_pubkey
+                .eq(maker_y_token_account.key)
+        {
+            msg!("Escrow Signed Order: Passed maker token accounts mismatch with the order's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let clock = Clock::get()?;
+        if clock.unix_timestamp >= signed_order.expiry_timestamp {
+            msg!("Escrow Signed Order: Order has expired");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Signed orders get their own delegate, so that they can't use up the allowance of the maker's delegated escrows
+        let (order_pda, order_pda_bump) = Pubkey::find_program_address(
+            &[b"order", &signed_order.maker_pubkey.to_bytes()],
+            program_id,
+        );
+        if !order_pda.eq(order_pda_account.key) {
+            msg!("Escrow Signed Order: Order PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the maker still lets the PDA move enough X
+        let maker_x_token_account_state = TokenState::unpack(&maker_x_token_account.data.borrow())?;
+        if !maker_x_token_account_state
+            .owner
+            .eq(&signed_order.maker_pubkey)
+        {
+            msg!("Escrow Signed Order: Maker's X Token account is not owned by the maker");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if maker_x_token_account_state.delegate != COption::Some(order_pda)
+            || maker_x_token_account_state.delegated_amount < signed_order.x_token_amount
+        {
+            msg!("Escrow Signed Order: Maker has not delegated enough X Tokens to the Order PDA");
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let mut maker_nonce_state = Self::prepare_maker_nonce_account(
+            program_id,
+            taker_account,
+            &signed_order.maker_pubkey,
+            maker_nonce_account,
+            system_program,
+        )?;
+
+        // Each nonce can only be filled once
+        if maker_nonce_state.nonce != signed_order.nonce {
+            msg!(
+                "Escrow Signed Order: Order nonce is stale, expected {}",
+                maker_nonce_state.nonce
+            );
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        maker_nonce_state.nonce = maker_nonce_state
+            .nonce
+            .checked_add(1)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        maker_nonce_state.serialize(&mut (&mut maker_nonce_account.data.borrow_mut()[..]))?;
+
+        msg!("Transferring Y Tokens to the maker");
+        Self::transfer_tokens(
+            token_program,
+            taker_y_token_account,
+            maker_y_token_account,
+            taker_account,
+            &[],
+            signed_order.y_token_amount,
+        )?;
+
+        msg!("Transferring X Tokens to the taker");
+        Self::transfer_tokens(
+            token_program,
+            maker_x_token_account,
+            taker_x_token_account,
+            order_pda_account,
+            &[&[
+                b"order",
+                &signed_order.maker_pubkey.to_bytes(),
+                &[order_pda_bump],
+            ]],
+            signed_order.x_token_amount,
+        )
+    }
+
+    // Increment maker nonce processor
+    fn process_increment_maker_nonce(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let maker_account = next_account_info(accounts_iterable)?;
+        let maker_nonce_account = next_account_info(accounts_iterable)?;
+        let system_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the maker has signed the transaction
+        if !maker_account.is_signer {
+            msg!("Escrow Nonce: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let mut maker_nonce_state = Self::prepare_maker_nonce_account(
+            program_id,
+            maker_account,
+            maker_account.key,
+            maker_nonce_account,
+            system_program,
+        )?;
+
+        maker_nonce_state.nonce = maker_nonce_state
+            .nonce
+            .checked_add(1)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        msg!("MAKER NONCE: {}", maker_nonce_state.nonce);
+
+        maker_nonce_state.serialize(&mut (&mut maker_nonce_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Reads the maker's nonce account, creating it (payer pays the rent) when it does not exist yet
+    fn prepare_maker_nonce_account<'a>(
+        program_id: &Pubkey,
+        payer_account: &AccountInfo<'a>,
+        maker_pubkey: &Pubkey,
+        maker_nonce_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> Result<MakerNonceState, ProgramError> {
+        let (maker_nonce_pubkey, maker_nonce_bump) =
+            Pubkey::find_program_address(&[b"nonce", &maker_pubkey.to_bytes()], program_id);
+        if !maker_nonce_pubkey.eq(maker_nonce_account.key) {
+            msg!("Maker Nonce Account mismatch");
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        if maker_nonce_account.data_is_empty() {
+            msg!("Creating the Maker Nonce Account");
+            Self::create_program_account(
+                program_id,
+                payer_account,
+                maker_nonce_account,
+                system_program,
+                &[b"nonce", &maker_pubkey.to_bytes(), &[maker_nonce_bump]],
+                MakerNonceState::LEN,
+            )?;
+
+            return Ok(MakerNonceState {
+                account_type: AccountType::MakerNonce,
+                maker_pubkey: *maker_pubkey,
+                nonce: 0,
+                bump: maker_nonce_bump,
+            });
+        }
+
+        if !maker_nonce_account.owner.eq(program_id) {
+            msg!("Incorrect Maker Nonce Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let maker_nonce_state = MakerNonceState::try_from_slice(&maker_nonce_account.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if maker_nonce_state.account_type != AccountType::MakerNonce {
+            msg!("Passed account is not a Maker Nonce Account");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(maker_nonce_state)
+    }
+
+    // Initialize milestone escrow processor
+    fn process_initialize_milestone_escrow(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        final_deadline: i64,
+        milestones: Vec<Milestone>,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let payer_account = next_account_info(accounts_iterable)?;
+        let payer_token_account = next_account_info(accounts_iterable)?;
+        let payee_token_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let milestone_escrow_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the payer has signed the transaction
+        if !payer_account.is_signer {
+            msg!("Milestone Initialize: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed state account is owned by this program
+        if !milestone_escrow_account.owner.eq(program_id) {
+            msg!("Milestone Initialize: Incorrect Milestone Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Checking if the state account is rent exempt
+        let rent = Rent::get()?;
+        let milestone_escrow_account_storage_size = milestone_escrow_account.data_len();
+        if !rent.is_exempt(
+            milestone_escrow_account.lamports(),
+            milestone_escrow_account_storage_size,
+        ) {
+            msg!("Milestone Initialize: State account is not rent exempt");
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        // Checking if the state has already been initialized (only empty accounts can be initialized)
+        let mut milestone_escrow_state =
+            MilestoneEscrowState::unpack_from_slice(&milestone_escrow_account.data.borrow())?;
+        if milestone_escrow_state.account_type != AccountType::Uninitialized {
+            msg!("Milestone Initialize: Account already initialized");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // Checking if the milestones are valid
+        let clock = Clock::get()?;
+        if final_deadline <= clock.unix_timestamp {
+            msg!("Milestone Initialize: Final deadline has to be in the future");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if milestones.is_empty() || milestones.len() > MAX_MILESTONES {
+            msg!(
+                "Milestone Initialize: Between 1 and {} milestones are allowed",
+                MAX_MILESTONES
+            );
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let mut deposit_amount: u64 = 0;
+        for milestone in milestones.iter() {
+            if milestone.amount == 0 || milestone.is_released {
+                msg!("Milestone Initialize: Milestones need a positive amount and can't be released yet");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            if milestone.deadline > final_deadline {
+                msg!("Milestone Initialize: Milestone deadlines can't be after the final deadline");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            deposit_amount = deposit_amount
+                .checked_add(milestone.amount)
+                .ok_or(ProgramError::InvalidInstructionData)?;
+        }
+
+        // Checking if the payee's token account can receive the payer's tokens
+        let payer_token_account_state = TokenState::unpack(&payer_token_account.data.borrow())?;
+        if !spl_token::check_id(payee_token_account.owner) {
+            msg!("Milestone Initialize: Payee's account is not a token account");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let payee_token_account_state = TokenState::unpack(&payee_token_account.data.borrow())?;
+        if !payee_token_account_state
+            .mint
+            .eq(&payer_token_account_state.mint)
+        {
+            msg!("Milestone Initialize: Payee's token account mint mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Self::validate_empty_vault(
+            vault_account,
+            payer_account.key,
+            &payer_token_account_state.mint,
+        )?;
+
+        let (vault_authority_address, vault_authority_bump) = Pubkey::find_program_address(
+            &[b"milestone", &milestone_escrow_account.key.to_bytes()],
+            program_id,
+        );
+
+        // BUSINESS LOGIC STARTS
+        milestone_escrow_state.account_type = AccountType::MilestoneEscrow;
+        milestone_escrow_state.payer_pubkey = *payer_account.key;
+        milestone_escrow_state.payee_token_pubkey = *payee_token_account.key;
+        milestone_escrow_state.vault_pubkey = *vault_account.key;
+        milestone_escrow_state.vault_authority_bump = vault_authority_bump;
+        milestone_escrow_state.final_deadline = final_deadline;
+        milestone_escrow_state.milestones = milestones;
+
+        // Checking if the state account has enough space for the milestones
+        let milestone_escrow_state_size = milestone_escrow_state.try_to_vec()?.len();
+        if milestone_escrow_state_size > milestone_escrow_account_storage_size {
+            msg!(
+                "Milestone Initialize: State account needs {} bytes of space",
+                milestone_escrow_state_size
+            );
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+
+        milestone_escrow_state
+            .serialize(&mut (&mut milestone_escrow_account.data.borrow_mut()[..]))?;
+
+        msg!("Transferring the deposit to the Vault");
+        Self::transfer_tokens(
+            token_program,
+            payer_token_account,
+            vault_account,
+            payer_account,
+            &[],
+            deposit_amount,
+        )?;
+
+        msg!("Transferring Vault ownership to the PDA");
+        Self::transfer_token_account_ownership(
+            token_program,
+            vault_account,
+            payer_account,
+            &vault_authority_address,
+        )
+    }
+
+    // Release milestone processor
+    fn process_release_milestone(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        milestone_index: u8,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let payer_account = next_account_info(accounts_iterable)?;
+        let milestone_escrow_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let payee_token_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        let mut milestone_escrow_state = Self::unpack_milestone_escrow_state(
+            program_id,
+            payer_account,
+            milestone_escrow_account,
+            vault_account,
+        )?;
+
+        // Checking if the payee's token account is the one stored in the state
+        if !milestone_escrow_state
+            .payee_token_pubkey
+            .eq(payee_token_account.key)
+        {
+            msg!("Milestone Release: Passed payee's token account mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"milestone",
+            &milestone_escrow_account.key.to_bytes(),
+            &[milestone_escrow_state.vault_authority_bump],
+        ];
+
+        // Checking if the pda matches with the pda passed
+        let checking_pda_address =
+            Pubkey::create_program_address(vault_authority_seed_bump_combination, program_id)?;
+        if !checking_pda_address.eq(vault_authority_account.key) {
+            msg!("Milestone Release: PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let milestone = milestone_escrow_state
+            .milestones
+            .get_mut(milestone_index as usize)
+            .ok_or(ProgramError::InvalidArgument)?;
+        if milestone.is_released {
+            msg!("Milestone Release: Milestone has already been released");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // BUSINESS LOGIC STARTS
+        msg!(
+            "Releasing milestone {} ({}) to the payee",
+            milestone_index,
+            milestone.amount
+        );
+        Self::transfer_tokens(
+            token_program,
+            vault_account,
+            payee_token_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+            milestone.amount,
+        )?;
+        milestone.is_released = true;
+
+        // The last tranche closes the escrow, anything else sent to the vault going to the payee as well
+        if milestone_escrow_state.unreleased_amount()? == 0 {
+            msg!("Closing the Vault");
+            Self::sweep_and_close_vault(
+                token_program,
+                vault_account,
+                payee_token_account,
+                payer_account,
+                vault_authority_account,
+                &[vault_authority_seed_bump_combination],
+            )?;
+
+            msg!("Closing the Milestone Escrow State Account");
+            return Self::close_program_account(milestone_escrow_account, payer_account);
+        }
+
+        milestone_escrow_state
+            .serialize(&mut (&mut milestone_escrow_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Refund milestones processor
+    fn process_refund_milestones(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let payer_account = next_account_info(accounts_iterable)?;
+        let milestone_escrow_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let payer_token_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        let milestone_escrow_state = Self::unpack_milestone_escrow_state(
+            program_id,
+            payer_account,
+            milestone_escrow_account,
+            vault_account,
+        )?;
+
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"milestone",
+            &milestone_escrow_account.key.to_bytes(),
+            &[milestone_escrow_state.vault_authority_bump],
+        ];
+
+        // Checking if the pda matches with the pda passed
+        let checking_pda_address =
+            Pubkey::create_program_address(vault_authority_seed_bump_combination, program_id)?;
+        if !checking_pda_address.eq(vault_authority_account.key) {
+            msg!("Milestone Refund: PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the final deadline has passed
+        let clock = Clock::get()?;
+        if clock.unix_timestamp < milestone_escrow_state.final_deadline {
+            msg!("Milestone Refund: Final deadline has not passed yet");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // BUSINESS LOGIC STARTS
+        // Whatever sits in the vault goes back, so that it can be closed
+        let vault_state = TokenState::unpack(&vault_account.data.borrow())?;
+        msg!(
+            "Refunding {} unreleased tokens to the payer",
+            vault_state.amount
+        );
+        Self::transfer_tokens(
+            token_program,
+            vault_account,
+            payer_token_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+            vault_state.amount,
+        )?;
+
+        msg!("Closing the Vault");
+        Self::close_vault(
+            token_program,
+            vault_account,
+            payer_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+        )?;
+
+        msg!("Closing the Milestone Escrow State Account");
+        Self::close_program_account(milestone_escrow_account, payer_account)
+    }
+
+    // Reads a milestone escrow, making sure the payer signed and the vault is the escrow's
+    fn unpack_milestone_escrow_state(
+        program_id: &Pubkey,
+        payer_account: &AccountInfo,
+        milestone_escrow_account: &AccountInfo,
+        vault_account: &AccountInfo,
+    ) -> Result<MilestoneEscrowState, ProgramError> {
+        if !payer_account.is_signer {
+            msg!("Milestone: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if !milestone_escrow_account.owner.eq(program_id) {
+            msg!("Milestone: Incorrect Milestone Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let milestone_escrow_state =
+            MilestoneEscrowState::unpack_from_slice(&milestone_escrow_account.data.borrow())?;
+        if milestone_escrow_state.account_type != AccountType::MilestoneEscrow {
+            msg!("Milestone: Passed account is not a Milestone Escrow");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !milestone_escrow_state.payer_pubkey.eq(payer_account.key) {
+            msg!("Milestone: Caller is not the payer");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !milestone_escrow_state.vault_pubkey.eq(vault_account.key) {
+            msg!("Milestone: Passed Vault mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(milestone_escrow_state)
+    }
+
+    // Raise dispute processor
+    fn process_raise_dispute(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let party_account = next_account_info(accounts_iterable)?;
+        let escrow_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the party has signed the transaction
+        if !party_account.is_signer {
+            msg!("Escrow Dispute: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed escrow state account is owned by this program
+        if !escrow_account.owner.eq(program_id) {
+            msg!("Escrow Dispute: Incorrect Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut escrow_account_state =
+            EscrowState::unpack_from_slice(&escrow_account.data.borrow())?;
+        if !escrow_account_state.is_initialized() {
+            msg!("Escrow Dispute: Escrow is not initialized");
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        let alice_pubkey = escrow_account_state.alice_pubkey;
+        let arbitration = match &mut escrow_account_state.arbitration {
+            Some(arbitration) => arbitration,
+            None => {
+                msg!("Escrow Dispute: Escrow has no arbiter");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        };
+
+        // Checking if the caller is one of the parties
+        if !party_account.key.eq(&alice_pubkey)
+            && !party_account.key.eq(&arbitration.counterparty_pubkey)
+        {
+            msg!("Escrow Dispute: Caller is neither Alice nor the counterparty");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if arbitration.is_disputed {
+            msg!("Escrow Dispute: Escrow is already disputed");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Open fiat trades have nobody to dispute with yet
+        if arbitration.counterparty_pubkey == Pubkey::default() {
+            msg!("Escrow Dispute: Escrow has no counterparty yet");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // BUSINESS LOGIC STARTS
+        arbitration.is_disputed = true;
+        msg!("ESCROW DISPUTED BY {}", party_account.key.to_string());
+
+        escrow_account_state.serialize(&mut (&mut escrow_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Resolve processor
+    fn process_resolve(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        alice_basis_points: u16,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let arbiter_account = next_account_info(accounts_iterable)?;
+        let escrow_account = next_account_info(accounts_iterable)?;
+        let pda_temp_x_token_account = next_account_info(accounts_iterable)?;
+        let alice_x_token_account = next_account_info(accounts_iterable)?;
+        let counterparty_x_token_account = next_account_info(accounts_iterable)?;
+        let arbiter_x_token_account = next_account_info(accounts_iterable)?;
+        let rent_refund_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let escrow_pda_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the arbiter has signed the transaction
+        if !arbiter_account.is_signer {
+            msg!("Escrow Resolve: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed escrow state account is owned by this program
+        if !escrow_account.owner.eq(program_id) {
+            msg!("Escrow Resolve: Incorrect Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let escrow_account_state = EscrowState::unpack_from_slice(&escrow_account.data.borrow())?;
+
+        let arbitration = match &escrow_account_state.arbitration {
+            Some(arbitration) if escrow_account_state.is_initialized() => arbitration,
+            _ => {
+                msg!("Escrow Resolve: Escrow has no arbiter");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        };
+
+        // Checking if the caller is the escrow's arbiter and the escrow is disputed
+        if !arbitration.arbiter_pubkey.eq(arbiter_account.key) {
+            msg!("Escrow Resolve: Caller is not the escrow's arbiter");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !arbitration.is_disputed {
+            msg!("Escrow Resolve: Escrow is not disputed");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if alice_basis_points > TOTAL_BASIS_POINTS {
+            msg!("Escrow Resolve: Alice's share can't be more than the whole vault");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Checking if the passed accounts are the ones stored in the state
+        if !escrow_account_state
+            .alice_temp_x_token_pubkey
+            .eq(pda_temp_x_token_account.key)
+        {
+            msg!("Escrow Resolve: Passed Temporary X Token address mismatch with Escrow State's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !escrow_account_state
+            .rent_refund_pubkey
+            .eq(rent_refund_account.key)
+        {
+            msg!("Escrow Resolve: Passed rent refund address mismatch with Escrow state's rent refund address");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // The arbiter decides on the split, not on where the parties' shares go
+        let alice_x_token_account_state = TokenState::unpack(&alice_x_token_account.data.borrow())?;
+        let counterparty_x_token_account_state =
+            TokenState::unpack(&counterparty_x_token_account.data.borrow())?;
+        if !alice_x_token_account_state
+            .owner
+            .eq(&escrow_account_state.alice_pubkey)
+            || !counterparty_x_token_account_state
+                .owner
+                .eq(&arbitration.counterparty_pubkey)
+        {
+            msg!("Escrow Resolve: Passed X Token accounts are not owned by the parties");
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let pda_seed_bump_combination: &[&[u8]] = &[
+            b"escrow",
+            &escrow_account_state.alice_pubkey.to_bytes(),
+            &[escrow_account_state.escrow_pda_bump],
+        ];
+
+        // Checking if the pda matches with the pda passed
+        let checking_pda_address =
+            Pubkey::create_program_address(pda_seed_bump_combination, program_id)?;
+        if !checking_pda_address.eq(escrow_pda_account.key) {
+            msg!("Escrow Resolve: Escrow PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let pda_temp_x_token_account_state =
+            TokenState::unpack(&pda_temp_x_token_account.data.borrow())?;
+        let vault_amount = pda_temp_x_token_account_state.amount;
+
+        let arbiter_fee = Self::basis_points_of(vault_amount, arbitration.fee_basis_points)?;
+        let remaining_amount = vault_amount
+            .checked_sub(arbiter_fee)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let alice_amount = Self::basis_points_of(remaining_amount, alice_basis_points)?;
+        let counterparty_amount = remaining_amount
+            .checked_sub(alice_amount)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        msg!(
+            "ESCROW RESOLVED: {} to Alice, {} to the counterparty, {} to the arbiter",
+            alice_amount,
+            counterparty_amount,
+            arbiter_fee
+        );
+
+        for (destination_account, amount) in [
+            (arbiter_x_token_account, arbiter_fee),
+            (alice_x_token_account, alice_amount),
+            (counterparty_x_token_account, counterparty_amount),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+
+            Self::transfer_tokens(
+                token_program,
+                pda_temp_x_token_account,
+                destination_account,
+                escrow_pda_account,
+                &[pda_seed_bump_combination],
+                amount,
+            )?;
+        }
+
+        msg!("Closing the temporary X token account");
+        Self::close_vault(
+            token_program,
+            pda_temp_x_token_account,
+            rent_refund_account,
+            escrow_pda_account,
+            &[pda_seed_bump_combination],
+        )?;
+
+        msg!("Closing the escrow state account");
+        Self::close_program_account(escrow_account, rent_refund_account)
+    }
+
+    // Reserve fiat trade processor
+    fn process_reserve_fiat_trade(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let buyer_account = next_account_info(accounts_iterable)?;
+        let escrow_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the buyer has signed the transaction
+        if !buyer_account.is_signer {
+            msg!("Escrow Reserve: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let (mut escrow_account_state, mut fiat_trade) =
+            Self::unpack_fiat_trade_escrow(program_id, escrow_account)?;
+
+        if escrow_account_state.alice_pubkey.eq(buyer_account.key) {
+            msg!("Escrow Reserve: Alice can't buy her own trade");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // An unpaid reservation which timed out reopens the trade
+        let clock = Clock::get()?;
+        let is_reservation_expired = fiat_trade.status == FiatTradeStatus::Reserved
+            && clock.unix_timestamp >= fiat_trade.step_deadline;
+        if fiat_trade.status != FiatTradeStatus::Open && !is_reservation_expired {
+            msg!("Escrow Reserve: Fiat trade is already reserved");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // BUSINESS LOGIC STARTS
+        fiat_trade.status = FiatTradeStatus::Reserved;
+        fiat_trade.buyer_pubkey = *buyer_account.key;
+        fiat_trade.step_deadline = clock
+            .unix_timestamp
+            .checked_add(fiat_trade.payment_window)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        msg!(
+            "FIAT TRADE RESERVED BY {} UNTIL {}",
+            buyer_account.key.to_string(),
+            fiat_trade.step_deadline
+        );
+
+        // The buyer is the other party of any dispute from now on
+        if let Some(arbitration) = &mut escrow_account_state.arbitration {
+            arbitration.counterparty_pubkey = *buyer_account.key;
+        }
+        escrow_account_state.mode = EscrowMode::FiatTrade(fiat_trade);
+
+        escrow_account_state.serialize(&mut (&mut escrow_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Mark fiat paid processor
+    fn process_mark_fiat_paid(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let buyer_account = next_account_info(accounts_iterable)?;
+        let escrow_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the buyer has signed the transaction
+        if !buyer_account.is_signer {
+            msg!("Escrow Mark Paid: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let (mut escrow_account_state, mut fiat_trade) =
+            Self::unpack_fiat_trade_escrow(program_id, escrow_account)?;
+
+        // Checking if the caller holds an ongoing reservation
+        if fiat_trade.status != FiatTradeStatus::Reserved
+            || !fiat_trade.buyer_pubkey.eq(buyer_account.key)
+        {
+            msg!("Escrow Mark Paid: Fiat trade is not reserved by the caller");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let clock = Clock::get()?;
+        if clock.unix_timestamp >= fiat_trade.step_deadline {
+            msg!("Escrow Mark Paid: Payment window has already ended");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // BUSINESS LOGIC STARTS
+        fiat_trade.status = FiatTradeStatus::PaidMarked;
+        fiat_trade.step_deadline = clock
+            .unix_timestamp
+            .checked_add(fiat_trade.confirmation_window)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        msg!(
+            "FIAT PAYMENT MARKED AS SENT, TO BE CONFIRMED UNTIL {}",
+            fiat_trade.step_deadline
+        );
+        escrow_account_state.mode = EscrowMode::FiatTrade(fiat_trade);
+
+        escrow_account_state.serialize(&mut (&mut escrow_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Release fiat trade processor
+    fn process_release_fiat_trade(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let alice_account = next_account_info(accounts_iterable)?;
+        let escrow_account = next_account_info(accounts_iterable)?;
+        let pda_temp_x_token_account = next_account_info(accounts_iterable)?;
+        let buyer_x_token_account = next_account_info(accounts_iterable)?;
+        let rent_refund_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let escrow_pda_account = next_account_info(accounts_iterable)?;
+
+        // Checking if Alice has signed the transaction
+        if !alice_account.is_signer {
+            msg!("Escrow Release: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let (escrow_account_state, fiat_trade) =
+            Self::unpack_fiat_trade_escrow(program_id, escrow_account)?;
+
+        // Checking if the caller is the escrow's alice
+        if !escrow_account_state.alice_pubkey.eq(alice_account.key) {
+            msg!("Escrow Release: Caller is not the escrow's Alice");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fiat_trade.status == FiatTradeStatus::Open {
+            msg!("Escrow Release: Fiat trade has no buyer yet");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the passed accounts are the ones stored in the state
+        if !escrow_account_state
+            .alice_temp_x_token_pubkey
+            .eq(pda_temp_x_token_account.key)
+        {
+            msg!("Escrow Release: Passed Temporary X Token address mismatch with Escrow State's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !escrow_account_state
+            .rent_refund_pubkey
+            .eq(rent_refund_account.key)
+        {
+            msg!("Escrow Release: Passed rent refund address mismatch with Escrow state's rent refund address");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let pda_seed_bump_combination: &[&[u8]] = &[
+            b"escrow",
+            &escrow_account_state.alice_pubkey.to_bytes(),
+            &[escrow_account_state.escrow_pda_bump],
+        ];
+
+        // Checking if the pda matches with the pda passed
+        let checking_pda_address =
+            Pubkey::create_program_address(pda_seed_bump_combination, program_id)?;
+        if !checking_pda_address.eq(escrow_pda_account.key) {
+            msg!("Escrow Release: Escrow PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let pda_temp_x_token_account_state =
+            TokenState::unpack(&pda_temp_x_token_account.data.borrow())?;
+
+        Self::prepare_associated_token_account(
+            alice_account,
+            &fiat_trade.buyer_pubkey,
+            buyer_x_token_account,
+            &pda_temp_x_token_account_state.mint,
+            token_program,
+            accounts_iterable,
+        )?;
+
+        msg!("Transferring X Tokens to the buyer");
+        Self::transfer_tokens(
+            token_program,
+            pda_temp_x_token_account,
+            buyer_x_token_account,
+            escrow_pda_account,
+            &[pda_seed_bump_combination],
+            pda_temp_x_token_account_state.amount,
+        )?;
+
+        msg!("Closing the temporary X token account");
+        Self::close_vault(
+            token_program,
+            pda_temp_x_token_account,
+            rent_refund_account,
+            escrow_pda_account,
+            &[pda_seed_bump_combination],
+        )?;
+
+        msg!("Closing the escrow state account");
+        Self::close_program_account(escrow_account, rent_refund_account)
+    }
+
+    // Timeout fiat trade processor
+    fn process_timeout_fiat_trade(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let escrow_account = next_account_info(accounts_iterable)?;
+
+        let (mut escrow_account_state, mut fiat_trade) =
+            Self::unpack_fiat_trade_escrow(program_id, escrow_account)?;
+
+        let clock = Clock::get()?;
+        if fiat_trade.status == FiatTradeStatus::Open
+            || clock.unix_timestamp < fiat_trade.step_deadline
+        {
+            msg!("Escrow Timeout: Current step of the fiat trade has not timed out");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // BUSINESS LOGIC STARTS
+        match fiat_trade.status {
+            FiatTradeStatus::Reserved => {
+                msg!("Payment window ended, reopening the fiat trade");
+                fiat_trade.status = FiatTradeStatus::Open;
+                fiat_trade.buyer_pubkey = Pubkey::default();
+                fiat_trade.step_deadline = 0;
+
+                if let Some(arbitration) = &mut escrow_account_state.arbitration {
+                    arbitration.counterparty_pubkey = Pubkey::default();
+                }
+            }
+            _ => {
+                msg!("Confirmation window ended, escalating the fiat trade to the arbiter");
+                if let Some(arbitration) = &mut escrow_account_state.arbitration {
+                    arbitration.is_disputed = true;
+                }
+            }
+        }
+        escrow_account_state.mode = EscrowMode::FiatTrade(fiat_trade);
+
+        escrow_account_state.serialize(&mut (&mut escrow_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Reads an escrow which has to be an undisputed P2P fiat trade
+    fn unpack_fiat_trade_escrow(
+        program_id: &Pubkey,
+        escrow_account: &AccountInfo,
+    ) -> Result<(EscrowState, FiatTrade), ProgramError> {
+        if !escrow_account.owner.eq(program_id) {
+            msg!("Incorrect Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let escrow_account_state = EscrowState::unpack_from_slice(&escrow_account.data.borrow())?;
+        let fiat_trade = match &escrow_account_state.mode {
+            EscrowMode::FiatTrade(fiat_trade) if escrow_account_state.is_initialized() => {
+                fiat_trade.clone()
+            }
+            _ => {
+                msg!("Escrow is not a fiat trade");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        };
+
+        // Disputed fiat trades are left to the arbiter
+        if escrow_account_state.is_disputed() {
+            msg!("Fiat trade is disputed");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok((escrow_account_state, fiat_trade))
+    }
+
+    // Share of an amount in basis points (at most 10000), rounded down
+    fn basis_points_of(amount: u64, basis_points: u16) -> Result<u64, ProgramError> {
+        u128::from(amount)
+            .checked_mul(u128::from(basis_points))
+            .map(|share| (share / u128::from(TOTAL_BASIS_POINTS)) as u64)
+            .ok_or(ProgramError::InvalidArgument)
+    }
+
+    // Initialize vesting processor
+    fn process_initialize_vesting(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        schedule: VestingSchedule,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let depositor_account = next_account_info(accounts_iterable)?;
+        let depositor_token_account = next_account_info(accounts_iterable)?;
+        let beneficiary_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let vesting_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the depositor has signed the transaction
+        if !depositor_account.is_signer {
+            msg!("Vesting Initialize: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed state account is owned by this program
+        if !vesting_account.owner.eq(program_id) {
+            msg!("Vesting Initialize: Incorrect Vesting State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Checking if the state account is rent exempt
+        let rent = Rent::get()?;
+        if !rent.is_exempt(vesting_account.lamports(), vesting_account.data_len()) {
+            msg!("Vesting Initialize: State account is not rent exempt");
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        // Checking if the state has already been initialized (only empty accounts can be initialized)
+        let mut vesting_state = VestingState::try_from_slice(&vesting_account.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if vesting_state.account_type != AccountType::Uninitialized {
+            msg!("Vesting Initialize: Account already initialized");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // Checking if the schedule is valid
+        if schedule.total_amount == 0
+            || schedule.start_timestamp >= schedule.end_timestamp
+            || schedule.cliff_timestamp < schedule.start_timestamp
+            || schedule.cliff_timestamp > schedule.end_timestamp
+        {
+            msg!("Vesting Initialize: Schedule needs a positive amount and start <= cliff <= end with start < end");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if schedule.period <= 0
+            || schedule.period > schedule.end_timestamp - schedule.start_timestamp
+        {
+            msg!("Vesting Initialize: Period has to be positive and fit between start and end");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let depositor_token_account_state =
+            TokenState::unpack(&depositor_token_account.data.borrow())?;
+        Self::validate_empty_vault(
+            vault_account,
+            depositor_account.key,
+            &depositor_token_account_state.mint,
+        )?;
+
+        let (vault_authority_address, vault_authority_bump) = Pubkey::find_program_address(
+            &[b"vesting", &vesting_account.key.to_bytes()],
+            program_id,
+        );
+
+        // BUSINESS LOGIC STARTS
+        vesting_state.account_type = AccountType::Vesting;
+        vesting_state.depositor_pubkey = *depositor_account.key;
+        vesting_state.beneficiary_pubkey = *beneficiary_account.key;
+        vesting_state.vault_pubkey = *vault_account.key;
+        vesting_state.vault_authority_bump = vault_authority_bump;
+        vesting_state.schedule = schedule;
+        vesting_state.withdrawn_amount = 0;
+        vesting_state.revoked_timestamp = 0;
+
+        vesting_state.serialize(&mut (&mut vesting_account.data.borrow_mut()[..]))?;
+
+        msg!("Transferring the vested tokens to the Vault");
+        Self::transfer_tokens(
+            token_program,
+            depositor_token_account,
+            vault_account,
+            depositor_account,
+            &[],
+            vesting_state.schedule.total_amount,
+        )?;
+
+        msg!("Transferring Vault ownership to the PDA");
+        Self::transfer_token_account_ownership(
+            token_program,
+            vault_account,
+            depositor_account,
+            &vault_authority_address,
+        )
+    }
+
+    // Withdraw vested processor
+    fn process_withdraw_vested(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let beneficiary_account = next_account_info(accounts_iterable)?;
+        let vesting_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let beneficiary_token_account = next_account_info(accounts_iterable)?;
+        let depositor_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the beneficiary has signed the transaction
+        if !beneficiary_account.is_signer {
+            msg!("Vesting Withdraw: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut vesting_state = Self::unpack_vesting_state(
+            program_id,
+            vesting_account,
+            vault_account,
+            vault_authority_account,
+        )?;
+
+        // Checking if the passed accounts are the ones stored in the state
+        if !vesting_state.beneficiary_pubkey.eq(beneficiary_account.key) {
+            msg!("Vesting Withdraw: Caller is not the beneficiary");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !vesting_state.depositor_pubkey.eq(depositor_account.key) {
+            msg!("Vesting Withdraw: Passed depositor mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let clock = Clock::get()?;
+        let vested_amount = vesting_state.vested_amount(clock.unix_timestamp)?;
+        let withdrawable_amount = vested_amount
+            .checked_sub(vesting_state.withdrawn_amount)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if withdrawable_amount == 0 {
+            msg!("Vesting Withdraw: Nothing has vested since the last withdrawal");
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"vesting",
+            &vesting_account.key.to_bytes(),
+            &[vesting_state.vault_authority_bump],
+        ];
+
+        msg!("Withdrawing {} vested tokens", withdrawable_amount);
+        Self::transfer_tokens(
+            token_program,
+            vault_account,
+            beneficiary_token_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+            withdrawable_amount,
+        )?;
+        vesting_state.withdrawn_amount = vested_amount;
+
+        // Nothing is left once the whole schedule (or what vested before the revocation) has been withdrawn
+        let final_amount = vesting_state.vested_amount(i64::MAX)?;
+        if vesting_state.withdrawn_amount == final_amount {
+            // Anything else sent to the vault goes to the beneficiary along with the last tokens
+            msg!("Closing the Vault");
+            Self::sweep_and_close_vault(
+                token_program,
+                vault_account,
+                beneficiary_token_account,
+                depositor_account,
+                vault_authority_account,
+                &[vault_authority_seed_bump_combination],
+            )?;
+
+            msg!("Closing the Vesting State Account");
+            return Self::close_program_account(vesting_account, depositor_account);
+        }
+
+        vesting_state.serialize(&mut (&mut vesting_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Revoke vesting processor
+    fn process_revoke_vesting(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let depositor_account = next_account_info(accounts_iterable)?;
+        let vesting_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let depositor_token_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the depositor has signed the transaction
+        if !depositor_account.is_signer {
+            msg!("Vesting Revoke: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut vesting_state = Self::unpack_vesting_state(
+            program_id,
+            vesting_account,
+            vault_account,
+            vault_authority_account,
+        )?;
+
+        // Checking if the caller is the depositor of a revocable vesting
+        if !vesting_state.depositor_pubkey.eq(depositor_account.key) {
+            msg!("Vesting Revoke: Caller is not the depositor");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !vesting_state.schedule.is_revocable || vesting_state.revoked_timestamp != 0 {
+            msg!("Vesting Revoke: Vesting is not revocable or already revoked");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let clock = Clock::get()?;
+        let vested_amount = vesting_state.vested_amount(clock.unix_timestamp)?;
+        let unvested_amount = vesting_state
+            .schedule
+            .total_amount
+            .checked_sub(vested_amount)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        vesting_state.revoked_timestamp = clock.unix_timestamp;
+
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"vesting",
+            &vesting_account.key.to_bytes(),
+            &[vesting_state.vault_authority_bump],
+        ];
+
+        if unvested_amount > 0 {
+            msg!(
+                "Returning {} unvested tokens to the depositor",
+                unvested_amount
+            );
+            Self::transfer_tokens(
+                token_program,
+                vault_account,
+                depositor_token_account,
+                vault_authority_account,
+                &[vault_authority_seed_bump_combination],
+                unvested_amount,
+            )?;
+        }
+
+        // Everything vested has already been withdrawn, anything else sent to the vault goes back with the unvested tokens
+        if vesting_state.withdrawn_amount == vested_amount {
+            msg!("Closing the Vault");
+            Self::sweep_and_close_vault(
+                token_program,
+                vault_account,
+                depositor_token_account,
+                depositor_account,
+                vault_authority_account,
+                &[vault_authority_seed_bump_combination],
+            )?;
+
+            msg!("Closing the Vesting State Account");
+            return Self::close_program_account(vesting_account, depositor_account);
+        }
+
+        vesting_state.serialize(&mut (&mut vesting_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Reads a vesting state, making sure the vault and its authority are the vesting's
+    fn unpack_vesting_state(
+        program_id: &Pubkey,
+        vesting_account: &AccountInfo,
+        vault_account: &AccountInfo,
+        vault_authority_account: &AccountInfo,
+    ) -> Result<VestingState, ProgramError> {
+        if !vesting_account.owner.eq(program_id) {
+            msg!("Vesting: Incorrect Vesting State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let vesting_state = VestingState::try_from_slice(&vesting_account.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if vesting_state.account_type != AccountType::Vesting {
+            msg!("Vesting: Passed account is not a Vesting");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !vesting_state.vault_pubkey.eq(vault_account.key) {
+            msg!("Vesting: Passed Vault mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let checking_pda_address = Pubkey::create_program_address(
+            &[
+                b"vesting",
+                &vesting_account.key.to_bytes(),
+                &[vesting_state.vault_authority_bump],
+            ],
+            program_id,
+        )?;
+        if !checking_pda_address.eq(vault_authority_account.key) {
+            msg!("Vesting: PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(vesting_state)
+    }
+
+    // Initialize stream processor
+    fn process_initialize_stream(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        terms: StreamTerms,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let sender_account = next_account_info(accounts_iterable)?;
+        let sender_token_account = next_account_info(accounts_iterable)?;
+        let recipient_token_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let stream_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the sender has signed the transaction
+        if !sender_account.is_signer {
+            msg!("Stream Initialize: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed state account is owned by this program
+        if !stream_account.owner.eq(program_id) {
+            msg!("Stream Initialize: Incorrect Stream State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Checking if the state account is rent exempt
+        let rent = Rent::get()?;
+        if !rent.is_exempt(stream_account.lamports(), stream_account.data_len()) {
+            msg!("Stream Initialize: State account is not rent exempt");
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        // Checking if the state has already been initialized (only empty accounts can be initialized)
+        let mut stream_state = StreamState::try_from_slice(&stream_account.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if stream_state.account_type != AccountType::Uninitialized {
+            msg!("Stream Initialize: Account already initialized");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // Checking if the terms are valid
+        if terms.deposit_amount == 0 || terms.rate_per_second == 0 {
+            msg!("Stream Initialize: Deposit and rate have to be positive");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // Checking if the recipient's token account can receive the sender's tokens
+        let sender_token_account_state = TokenState::unpack(&sender_token_account.data.borrow())?;
+        if !spl_token::check_id(recipient_token_account.owner) {
+            msg!("Stream Initialize: Recipient's account is not a token account");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let recipient_token_account_state =
+            TokenState::unpack(&recipient_token_account.data.borrow())?;
+        if !recipient_token_account_state
+            .mint
+            .eq(&sender_token_account_state.mint)
+        {
+            msg!("Stream Initialize: Recipient's token account mint mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Self::validate_empty_vault(
+            vault_account,
+            sender_account.key,
+            &sender_token_account_state.mint,
+        )?;
+
+        let (vault_authority_address, vault_authority_bump) =
+            Pubkey::find_program_address(&[b"stream", &stream_account.key.to_bytes()], program_id);
+
+        // BUSINESS LOGIC STARTS
+        stream_state.account_type = AccountType::Stream;
+        stream_state.sender_pubkey = *sender_account.key;
+        stream_state.recipient_pubkey = recipient_token_account_state.owner;
+        stream_state.recipient_token_pubkey = *recipient_token_account.key;
+        stream_state.vault_pubkey = *vault_account.key;
+        stream_state.vault_authority_bump = vault_authority_bump;
+        stream_state.terms = terms;
+        stream_state.withdrawn_amount = 0;
+
+        stream_state.serialize(&mut (&mut stream_account.data.borrow_mut()[..]))?;
+
+        msg!("Transferring the deposit to the Vault");
+        Self::transfer_tokens(
+            token_program,
+            sender_token_account,
+            vault_account,
+            sender_account,
+            &[],
+            stream_state.terms.deposit_amount,
+        )?;
+
+        msg!("Transferring Vault ownership to the PDA");
+        Self::transfer_token_account_ownership(
+            token_program,
+            vault_account,
+            sender_account,
+            &vault_authority_address,
+        )
+    }
+
+    // Withdraw streamed processor
+    fn process_withdraw_streamed(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let recipient_account = next_account_info(accounts_iterable)?;
+        let stream_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let recipient_token_account = next_account_info(accounts_iterable)?;
+        let sender_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the recipient has signed the transaction
+        if !recipient_account.is_signer {
+            msg!("Stream Withdraw: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut stream_state = Self::unpack_stream_state(
+            program_id,
+            stream_account,
+            vault_account,
+            recipient_token_account,
+            sender_account,
+            vault_authority_account,
+        )?;
+
+        if !stream_state.recipient_pubkey.eq(recipient_account.key) {
+            msg!("Stream Withdraw: Caller is not the recipient");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let clock = Clock::get()?;
+        let accrued_amount = stream_state.terms.accrued_amount(clock.unix_timestamp);
+        let withdrawable_amount = accrued_amount
+            .checked_sub(stream_state.withdrawn_amount)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if withdrawable_amount == 0 {
+            msg!("Stream Withdraw: Nothing has been streamed since the last withdrawal");
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"stream",
+            &stream_account.key.to_bytes(),
+            &[stream_state.vault_authority_bump],
+        ];
+
+        msg!("Withdrawing {} streamed tokens", withdrawable_amount);
+        Self::transfer_tokens(
+            token_program,
+            vault_account,
+            recipient_token_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+            withdrawable_amount,
+        )?;
+        stream_state.withdrawn_amount = accrued_amount;
+
+        // The stream ends once the whole deposit has been withdrawn, anything else sent to the vault going to the recipient
+        if stream_state.withdrawn_amount == stream_state.terms.deposit_amount {
+            msg!("Closing the Vault");
+            Self::sweep_and_close_vault(
+                token_program,
+                vault_account,
+                recipient_token_account,
+                sender_account,
+                vault_authority_account,
+                &[vault_authority_seed_bump_combination],
+            )?;
+
+            msg!("Closing the Stream State Account");
+            return Self::close_program_account(stream_account, sender_account);
+        }
+
+        stream_state.serialize(&mut (&mut stream_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Close stream processor
+    fn process_close_stream(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let party_account = next_account_info(accounts_iterable)?;
+        let stream_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let recipient_token_account = next_account_info(accounts_iterable)?;
+        let sender_token_account = next_account_info(accounts_iterable)?;
+        let sender_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the party has signed the transaction
+        if !party_account.is_signer {
+            msg!("Stream Close: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let stream_state = Self::unpack_stream_state(
+            program_id,
+            stream_account,
+            vault_account,
+            recipient_token_account,
+            sender_account,
+            vault_authority_account,
+        )?;
+
+        // Checking if the caller is one of the parties
+        if !party_account.key.eq(&stream_state.sender_pubkey)
+            && !party_account.key.eq(&stream_state.recipient_pubkey)
+        {
+            msg!("Stream Close: Caller is neither the sender nor the recipient");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // The recipient could otherwise redirect the sender's remainder
+        let sender_token_account_state = TokenState::unpack(&sender_token_account.data.borrow())?;
+        if !sender_token_account_state
+            .owner
+            .eq(&stream_state.sender_pubkey)
+        {
+            msg!("Stream Close: Passed sender's token account is not owned by the sender");
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let clock = Clock::get()?;
+        let recipient_amount = stream_state
+            .terms
+            .accrued_amount(clock.unix_timestamp)
+            .checked_sub(stream_state.withdrawn_amount)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let vault_state = TokenState::unpack(&vault_account.data.borrow())?;
+        let sender_amount = vault_state
+            .amount
+            .checked_sub(recipient_amount)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"stream",
+            &stream_account.key.to_bytes(),
+            &[stream_state.vault_authority_bump],
+        ];
+
+        msg!(
+            "STREAM CLOSED: {} to the recipient, {} back to the sender",
+            recipient_amount,
+            sender_amount
+        );
+        for (destination_account, amount) in [
+            (recipient_token_account, recipient_amount),
+            (sender_token_account, sender_amount),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+
+            Self::transfer_tokens(
+                token_program,
+                vault_account,
+                destination_account,
+                vault_authority_account,
+                &[vault_authority_seed_bump_combination],
+                amount,
+            )?;
+        }
+
+        msg!("Closing the Vault");
+        Self::close_vault(
+            token_program,
+            vault_account,
+            sender_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+        )?;
+
+        msg!("Closing the Stream State Account");
+        Self::close_program_account(stream_account, sender_account)
+    }
+
+    // Reads a stream state, making sure the passed accounts are the stream's
+    fn unpack_stream_state(
+        program_id: &Pubkey,
+        stream_account: &AccountInfo,
+        vault_account: &AccountInfo,
+        recipient_token_account: &AccountInfo,
+        sender_account: &AccountInfo,
+        vault_authority_account: &AccountInfo,
+    ) -> Result<StreamState, ProgramError> {
+        if !stream_account.owner.eq(program_id) {
+            msg!("Stream: Incorrect Stream State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let stream_state = StreamState::try_from_slice(&stream_account.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if stream_state.account_type != AccountType::Stream {
+            msg!("Stream: Passed account is not a Stream");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !stream_state.vault_pubkey.eq(vault_account.key)
+            || !stream_state
+                .recipient_token_pubkey
+                .eq(recipient_token_account.key)
+            || !stream_state.sender_pubkey.eq(sender_account.key)
+        {
+            msg!("Stream: Passed accounts mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let checking_pda_address = Pubkey::create_program_address(
+            &[
+                b"stream",
+                &stream_account.key.to_bytes(),
+                &[stream_state.vault_authority_bump],
+            ],
+            program_id,
+        )?;
+        if !checking_pda_address.eq(vault_authority_account.key) {
+            msg!("Stream: PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(stream_state)
+    }
+
+    // Initialize campaign processor
+    fn process_initialize_campaign(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        goal_amount: u64,
+        deadline: i64,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let creator_account = next_account_info(accounts_iterable)?;
+        let creator_y_token_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let campaign_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the creator has signed the transaction
+        if !creator_account.is_signer {
+            msg!("Campaign Initialize: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed state account is owned by this program
+        if !campaign_account.owner.eq(program_id) {
+            msg!("Campaign Initialize: Incorrect Campaign State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Checking if the state account is rent exempt
+        let rent = Rent::get()?;
+        if !rent.is_exempt(campaign_account.lamports(), campaign_account.data_len()) {
+            msg!("Campaign Initialize: State account is not rent exempt");
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        // Checking if the state has already been initialized (only empty accounts can be initialized)
+        let mut campaign_state = CampaignState::try_from_slice(&campaign_account.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if campaign_state.account_type != AccountType::Uninitialized {
+            msg!("Campaign Initialize: Account already initialized");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // Checking if the goal and deadline are valid
+        let clock = Clock::get()?;
+        if goal_amount == 0 || deadline <= clock.unix_timestamp {
+            msg!("Campaign Initialize: Goal has to be positive and the deadline in the future");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if !spl_token::check_id(creator_y_token_account.owner) {
+            msg!("Campaign Initialize: Creator's account is not a token account");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let creator_y_token_account_state =
+            TokenState::unpack(&creator_y_token_account.data.borrow())?;
+        Self::validate_empty_vault(
+            vault_account,
+            creator_account.key,
+            &creator_y_token_account_state.mint,
+        )?;
+
+        let (vault_authority_address, vault_authority_bump) = Pubkey::find_program_address(
+            &[b"campaign", &campaign_account.key.to_bytes()],
+            program_id,
+        );
+
+        // BUSINESS LOGIC STARTS
+        campaign_state.account_type = AccountType::Campaign;
+        campaign_state.creator_pubkey = *creator_account.key;
+        campaign_state.creator_y_token_pubkey = *creator_y_token_account.key;
+        campaign_state.vault_pubkey = *vault_account.key;
+        campaign_state.vault_authority_bump = vault_authority_bump;
+        campaign_state.goal_amount = goal_amount;
+        campaign_state.deadline = deadline;
+        campaign_state.raised_amount = 0;
+        campaign_state.open_contributions = 0;
+        campaign_state.is_claimed = false;
+
+        campaign_state.serialize(&mut (&mut campaign_account.data.borrow_mut()[..]))?;
+
+        msg!("Transferring Vault ownership to the PDA");
+        Self::transfer_token_account_ownership(
+            token_program,
+            vault_account,
+            creator_account,
+            &vault_authority_address,
+        )
+    }
+
+    // Contribute processor
+    fn process_contribute(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let backer_account = next_account_info(accounts_iterable)?;
+        let backer_y_token_account = next_account_info(accounts_iterable)?;
+        let campaign_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let contribution_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let system_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the backer has signed the transaction
+        if !backer_account.is_signer {
+            msg!("Campaign Contribute: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut campaign_state =
+            Self::unpack_campaign_state(program_id, campaign_account, vault_account)?;
+
+        // Checking if the campaign is still running
+        let clock = Clock::get()?;
+        if clock.unix_timestamp >= campaign_state.deadline {
+            msg!("Campaign Contribute: Campaign has already ended");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if amount == 0 {
+            msg!("Campaign Contribute: Contribution has to be positive");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Checking if the contribution receipt is the backer's PDA for this campaign
+        let (contribution_pubkey, contribution_bump) = Pubkey::find_program_address(
+            &[
+                b"contribution",
+                &campaign_account.key.to_bytes(),
+                &backer_account.key.to_bytes(),
+            ],
+            program_id,
+        );
+        if !contribution_pubkey.eq(contribution_account.key) {
+            msg!("Campaign Contribute: Contribution Receipt mismatch");
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let mut contribution_state = if contribution_account.data_is_empty() {
+            msg!("Creating the Contribution Receipt");
+            Self::create_program_account(
+                program_id,
+                backer_account,
+                contribution_account,
+                system_program,
+                &[
+                    b"contribution",
+                    &campaign_account.key.to_bytes(),
+                    &backer_account.key.to_bytes(),
+                    &[contribution_bump],
+                ],
+                ContributionState::LEN,
+            )?;
+
+            campaign_state.open_contributions = campaign_state
+                .open_contributions
+                .checked_add(1)
+                .ok_or(ProgramError::InvalidAccountData)?;
+
+            ContributionState {
+                account_type: AccountType::Contribution,
+                campaign_pubkey: *campaign_account.key,
+                backer_pubkey: *backer_account.key,
+                amount: 0,
+                bump: contribution_bump,
+            }
+        } else {
+            Self::unpack_contribution_state(program_id, contribution_account, campaign_account.key)?
+        };
+
+        msg!("Transferring the contribution to the Vault");
+        Self::transfer_tokens(
+            token_program,
+            backer_y_token_account,
+            vault_account,
+            backer_account,
+            &[],
+            amount,
+        )?;
+
+        contribution_state.amount = contribution_state
+            .amount
+            .checked_add(amount)
+            .ok_or(ProgramError::InvalidArgument)?;
+        contribution_state.serialize(&mut (&mut contribution_account.data.borrow_mut()[..]))?;
+
+        campaign_state.raised_amount = campaign_state
+            .raised_amount
+            .checked_add(amount)
+            .ok_or(ProgramError::InvalidArgument)?;
+        msg!(
+            "CAMPAIGN RAISED {} OF {}",
+            campaign_state.raised_amount,
+            campaign_state.goal_amount
+        );
+        campaign_state.serialize(&mut (&mut campaign_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Claim campaign processor
+    fn process_claim_campaign(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let creator_account = next_account_info(accounts_iterable)?;
+        let campaign_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let creator_y_token_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the creator has signed the transaction
+        if !creator_account.is_signer {
+            msg!("Campaign Claim: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut campaign_state =
+            Self::unpack_campaign_state(program_id, campaign_account, vault_account)?;
+
+        // Checking if the passed accounts are the ones stored in the state
+        if !campaign_state.creator_pubkey.eq(creator_account.key)
+            || !campaign_state
+                .creator_y_token_pubkey
+                .eq(creator_y_token_account.key)
+        {
+            msg!("Campaign Claim: Passed creator accounts mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"campaign",
+            &campaign_account.key.to_bytes(),
+            &[campaign_state.vault_authority_bump],
+        ];
+
+        // Checking if the pda matches with the pda passed
+        let checking_pda_address =
+            Pubkey::create_program_address(vault_authority_seed_bump_combination, program_id)?;
+        if !checking_pda_address.eq(vault_authority_account.key) {
+            msg!("Campaign Claim: PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the campaign succeeded
+        let clock = Clock::get()?;
+        if clock.unix_timestamp < campaign_state.deadline {
+            msg!("Campaign Claim: Campaign is still running");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // A failed campaign without open receipts (nobody backed it) has nothing to refund, so the creator closes it instead
+        if (!campaign_state.is_goal_met() && campaign_state.open_contributions != 0)
+            || campaign_state.is_claimed
+        {
+            msg!("Campaign Claim: Campaign missed its goal or has already been claimed");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let vault_state = TokenState::unpack(&vault_account.data.borrow())?;
+        msg!("Claiming {} pooled tokens", vault_state.amount);
+        Self::transfer_tokens(
+            token_program,
+            vault_account,
+            creator_y_token_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+            vault_state.amount,
+        )?;
+
+        msg!("Closing the Vault");
+        Self::close_vault(
+            token_program,
+            vault_account,
+            creator_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+        )?;
+
+        // The campaign stays around until every backer closed their receipt
+        if campaign_state.open_contributions == 0 {
+            msg!("Closing the Campaign State Account");
+            return Self::close_program_account(campaign_account, creator_account);
+        }
+
+        campaign_state.is_claimed = true;
+        campaign_state.serialize(&mut (&mut campaign_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Refund contribution processor
+    fn process_refund_contribution(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let backer_account = next_account_info(accounts_iterable)?;
+        let campaign_account = next_account_info(accounts_iterable)?;
+        let contribution_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let backer_y_token_account = next_account_info(accounts_iterable)?;
+        let creator_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+        let creator_y_token_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the backer has signed the transaction
+        if !backer_account.is_signer {
+            msg!("Campaign Refund: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut campaign_state =
+            Self::unpack_campaign_state(program_id, campaign_account, vault_account)?;
+        let contribution_state = Self::unpack_contribution_state(
+            program_id,
+            contribution_account,
+            campaign_account.key,
+        )?;
+
+        // Checking if the passed accounts are the ones stored in the states
+        if !contribution_state.backer_pubkey.eq(backer_account.key) {
+            msg!("Campaign Refund: Caller is not the receipt's backer");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !campaign_state.creator_pubkey.eq(creator_account.key)
+            || !campaign_state
+                .creator_y_token_pubkey
+                .eq(creator_y_token_account.key)
+        {
+            msg!("Campaign Refund: Passed creator accounts mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"campaign",
+            &campaign_account.key.to_bytes(),
+            &[campaign_state.vault_authority_bump],
+        ];
+
+        // Checking if the pda matches with the pda passed
+        let checking_pda_address =
+            Pubkey::create_program_address(vault_authority_seed_bump_combination, program_id)?;
+        if !checking_pda_address.eq(vault_authority_account.key) {
+            msg!("Campaign Refund: PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the campaign has ended
+        let clock = Clock::get()?;
+        if clock.unix_timestamp < campaign_state.deadline {
+            msg!("Campaign Refund: Campaign is still running");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // BUSINESS LOGIC STARTS
+        // Deposits of a successful campaign belong to the creator, only the receipt's rent goes back
+        let is_goal_met = campaign_state.is_goal_met();
+        if !is_goal_met {
+            msg!(
+                "Refunding {} tokens to the backer",
+                contribution_state.amount
+            );
+            Self::transfer_tokens(
+                token_program,
+                vault_account,
+                backer_y_token_account,
+                vault_authority_account,
+                &[vault_authority_seed_bump_combination],
+                contribution_state.amount,
+            )?;
+        }
+
+        msg!("Closing the Contribution Receipt");
+        Self::close_program_account(contribution_account, backer_account)?;
+
+        campaign_state.open_contributions = campaign_state
+            .open_contributions
+            .checked_sub(1)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        // The last receipt of a failed or claimed campaign closes it
+        if campaign_state.open_contributions == 0 && (!is_goal_met || campaign_state.is_claimed) {
+            // Every deposit has been refunded by now, anything else sent to the vault goes to the creator
+            if !is_goal_met {
+                msg!("Closing the Vault");
+                Self::sweep_and_close_vault(
+                    token_program,
+                    vault_account,
+                    creator_y_token_account,
+                    creator_account,
+                    vault_authority_account,
+                    &[vault_authority_seed_bump_combination],
+                )?;
+            }
+
+            msg!("Closing the Campaign State Account");
+            return Self::close_program_account(campaign_account, creator_account);
+        }
+
+        campaign_state.serialize(&mut (&mut campaign_account.data.borrow_mut()[..]))?;
+
+        Ok(())
+    }
+
+    // Reads a campaign state, making sure the vault is the campaign's
+    fn unpack_campaign_state(
+        program_id: &Pubkey,
+        campaign_account: &AccountInfo,
+        vault_account: &AccountInfo,
+    ) -> Result<CampaignState, ProgramError> {
+        if !campaign_account.owner.eq(program_id) {
+            msg!("Campaign: Incorrect Campaign State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let campaign_state = CampaignState::try_from_slice(&campaign_account.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if campaign_state.account_type != AccountType::Campaign {
+            msg!("Campaign: Passed account is not a Campaign");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !campaign_state.vault_pubkey.eq(vault_account.key) {
+            msg!("Campaign: Passed Vault mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(campaign_state)
+    }
+
+    // Reads a contribution receipt, making sure it is a receipt of the passed campaign owned by this program
+    fn unpack_contribution_state(
+        program_id: &Pubkey,
+        contribution_account: &AccountInfo,
+        campaign_pubkey: &Pubkey,
+    ) -> Result<ContributionState, ProgramError> {
+        if !contribution_account.owner.eq(program_id) {
+            msg!("Incorrect Contribution Receipt passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let contribution_state =
+            ContributionState::try_from_slice(&contribution_account.data.borrow())
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+        if contribution_state.account_type != AccountType::Contribution
+            || !contribution_state.campaign_pubkey.eq(campaign_pubkey)
+        {
+            msg!("Passed Contribution Receipt does not belong to the campaign");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(contribution_state)
+    }
+
+    // Initialize bounty processor
+    fn process_initialize_bounty(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        reward_amount: u64,
+        expiry_timestamp: i64,
+        approvers: Vec<Pubkey>,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let poster_account = next_account_info(accounts_iterable)?;
+        let poster_token_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let bounty_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the poster has signed the transaction
+        if !poster_account.is_signer {
+            msg!("Bounty Initialize: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed state account is owned by this program
+        if !bounty_account.owner.eq(program_id) {
+            msg!("Bounty Initialize: Incorrect Bounty State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Checking if the state account is rent exempt
+        let rent = Rent::get()?;
+        let bounty_account_storage_size = bounty_account.data_len();
+        if !rent.is_exempt(bounty_account.lamports(), bounty_account_storage_size) {
+            msg!("Bounty Initialize: State account is not rent exempt");
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        // Checking if the state has already been initialized (only empty accounts can be initialized)
+        let mut bounty_state = BountyState::unpack_from_slice(&bounty_account.data.borrow())?;
+        if bounty_state.account_type != AccountType::Uninitialized {
+            msg!("Bounty Initialize: Account already initialized");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // Checking if the bounty terms are valid
+        let clock = Clock::get()?;
+        if reward_amount == 0 || expiry_timestamp <= clock.unix_timestamp {
+            msg!("Bounty Initialize: Reward has to be positive and the expiry in the future");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if approvers.is_empty() || approvers.len() > MAX_BOUNTY_APPROVERS {
+            msg!(
+                "Bounty Initialize: Between 1 and {} approvers are allowed",
+                MAX_BOUNTY_APPROVERS
+            );
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let poster_token_account_state = TokenState::unpack(&poster_token_account.data.borrow())?;
+        Self::validate_empty_vault(
+            vault_account,
+            poster_account.key,
+            &poster_token_account_state.mint,
+        )?;
+
+        let (vault_authority_address, vault_authority_bump) =
+            Pubkey::find_program_address(&[b"bounty", &bounty_account.key.to_bytes()], program_id);
+
+        // BUSINESS LOGIC STARTS
+        bounty_state.account_type = AccountType::Bounty;
+        bounty_state.poster_pubkey = *poster_account.key;
+        bounty_state.vault_pubkey = *vault_account.key;
+        bounty_state.vault_authority_bump = vault_authority_bump;
+        bounty_state.expiry_timestamp = expiry_timestamp;
+        bounty_state.approvers = approvers;
+
+        // Checking if the state account has enough space for the approvers
+        let bounty_state_size = bounty_state.try_to_vec()?.len();
+        if bounty_state_size > bounty_account_storage_size {
+            msg!(
+                "Bounty Initialize: State account needs {} bytes of space",
+                bounty_state_size
+            );
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+
+        bounty_state.serialize(&mut (&mut bounty_account.data.borrow_mut()[..]))?;
+
+        msg!("Transferring the reward to the Vault");
+        Self::transfer_tokens(
+            token_program,
+            poster_token_account,
+            vault_account,
+            poster_account,
+            &[],
+            reward_amount,
+        )?;
+
+        msg!("Transferring Vault ownership to the PDA");
+        Self::transfer_token_account_ownership(
+            token_program,
+            vault_account,
+            poster_account,
+            &vault_authority_address,
+        )
+    }
+
+    // Award processor
+    fn process_award(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        hunter_basis_points: &[u16],
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let approver_account = next_account_info(accounts_iterable)?;
+        let bounty_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let poster_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the approver has signed the transaction
+        if !approver_account.is_signer {
+            msg!("Bounty Award: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let bounty_state = Self::unpack_bounty_state(
+            program_id,
+            bounty_account,
+            vault_account,
+            poster_account,
+            vault_authority_account,
+        )?;
+
+        // Checking if the caller is one of the approvers
+        if !bounty_state.approvers.contains(approver_account.key) {
+            msg!("Bounty Award: Caller is not an approver");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the shares are valid
+        if hunter_basis_points.is_empty() || hunter_basis_points.len() > MAX_BOUNTY_HUNTERS {
+            msg!(
+                "Bounty Award: Between 1 and {} hunters are allowed",
+                MAX_BOUNTY_HUNTERS
+            );
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let total_basis_points = hunter_basis_points
+            .iter()
+            .try_fold(0u16, |total, basis_points| total.checked_add(*basis_points))
+            .ok_or(ProgramError::InvalidArgument)?;
+        if total_basis_points != TOTAL_BASIS_POINTS {
+            msg!(
+                "Bounty Award: Hunter shares add up to {} instead of {}",
+                total_basis_points,
+                TOTAL_BASIS_POINTS
+            );
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"bounty",
+            &bounty_account.key.to_bytes(),
+            &[bounty_state.vault_authority_bump],
+        ];
+
+        let vault_state = TokenState::unpack(&vault_account.data.borrow())?;
+        let mut remaining_amount = vault_state.amount;
+        for (index, basis_points) in hunter_basis_points.iter().enumerate() {
+            let hunter_token_account = next_account_info(accounts_iterable)?;
+
+            // The last hunter gets the rounding dust
+            let amount = if index == hunter_basis_points.len() - 1 {
+                remaining_amount
+            } else {
+                Self::basis_points_of(vault_state.amount, *basis_points)?
+            };
+            remaining_amount = remaining_amount
+                .checked_sub(amount)
+                .ok_or(ProgramError::InvalidArgument)?;
+
+            if amount == 0 {
+                continue;
+            }
+
+            msg!(
+                "Awarding {} to {}",
+                amount,
+                hunter_token_account.key.to_string()
+            );
+            Self::transfer_tokens(
+                token_program,
+                vault_account,
+                hunter_token_account,
+                vault_authority_account,
+                &[vault_authority_seed_bump_combination],
+                amount,
+            )?;
+        }
+
+        msg!("Closing the Vault");
+        Self::close_vault(
+            token_program,
+            vault_account,
+            poster_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+        )?;
+
+        msg!("Closing the Bounty State Account");
+        Self::close_program_account(bounty_account, poster_account)
+    }
+
+    // Reclaim bounty processor
+    fn process_reclaim_bounty(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let poster_account = next_account_info(accounts_iterable)?;
+        let bounty_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let poster_token_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the poster has signed the transaction
+        if !poster_account.is_signer {
+            msg!("Bounty Reclaim: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let bounty_state = Self::unpack_bounty_state(
+            program_id,
+            bounty_account,
+            vault_account,
+            poster_account,
+            vault_authority_account,
+        )?;
+
+        // Checking if the bounty has expired
+        let clock = Clock::get()?;
+        if clock.unix_timestamp < bounty_state.expiry_timestamp {
+            msg!("Bounty Reclaim: Bounty has not expired yet");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"bounty",
+            &bounty_account.key.to_bytes(),
+            &[bounty_state.vault_authority_bump],
+        ];
+
+        let vault_state = TokenState::unpack(&vault_account.data.borrow())?;
+        msg!(
+            "Returning the reward of {} to the poster",
+            vault_state.amount
+        );
+        Self::transfer_tokens(
+            token_program,
+            vault_account,
+            poster_token_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+            vault_state.amount,
+        )?;
+
+        msg!("Closing the Vault");
+        Self::close_vault(
+            token_program,
+            vault_account,
+            poster_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+        )?;
+
+        msg!("Closing the Bounty State Account");
+        Self::close_program_account(bounty_account, poster_account)
+    }
+
+    // Reads a bounty state, making sure the passed accounts are the bounty's
+    fn unpack_bounty_state(
+        program_id: &Pubkey,
+        bounty_account: &AccountInfo,
+        vault_account: &AccountInfo,
+        poster_account: &AccountInfo,
+        vault_authority_account: &AccountInfo,
+    ) -> Result<BountyState, ProgramError> {
+        if !bounty_account.owner.eq(program_id) {
+            msg!("Bounty: Incorrect Bounty State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let bounty_state = BountyState::unpack_from_slice(&bounty_account.data.borrow())?;
+        if bounty_state.account_type != AccountType::Bounty {
+            msg!("Bounty: Passed account is not a Bounty");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !bounty_state.vault_pubkey.eq(vault_account.key)
+            || !bounty_state.poster_pubkey.eq(poster_account.key)
+        {
+            msg!("Bounty: Passed accounts mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let checking_pda_address = Pubkey::create_program_address(
+            &[
+                b"bounty",
+                &bounty_account.key.to_bytes(),
+                &[bounty_state.vault_authority_bump],
+            ],
+            program_id,
+        )?;
+        if !checking_pda_address.eq(vault_authority_account.key) {
+            msg!("Bounty: PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(bounty_state)
+    }
+
+    // Reads a collection bid state, making sure the passed accounts are the bid's
+    fn unpack_collection_bid_state(
+        program_id: &Pubkey,
+        collection_bid_account: &AccountInfo,
+        vault_account: &AccountInfo,
+        buyer_account: &AccountInfo,
+        vault_authority_account: &AccountInfo,
+    ) -> Result<CollectionBidState, ProgramError> {
+        if !collection_bid_account.owner.eq(program_id) {
+            msg!("Collection Bid: Incorrect Collection Bid State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let collection_bid_state =
+            CollectionBidState::try_from_slice(&collection_bid_account.data.borrow())
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+        if collection_bid_state.account_type != AccountType::CollectionBid {
+            msg!("Collection Bid: Passed account is not a Collection Bid");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !collection_bid_state.vault_pubkey.eq(vault_account.key)
+            || !collection_bid_state.buyer_pubkey.eq(buyer_account.key)
+        {
+            msg!("Collection Bid: Passed accounts mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let checking_pda_address = Pubkey::create_program_address(
+            &[
+                b"collection_bid",
+                &collection_bid_account.key.to_bytes(),
+                &[collection_bid_state.vault_authority_bump],
+            ],
+            program_id,
+        )?;
+        if !checking_pda_address.eq(vault_authority_account.key) {
+            msg!("Collection Bid: PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(collection_bid_state)
+    }
+
+    // Reads a mutual escrow state owned by this program
+    fn unpack_mutual_escrow_state(
+        program_id: &Pubkey,
+        mutual_escrow_account: &AccountInfo,
+    ) -> Result<MutualEscrowState, ProgramError> {
+        if !mutual_escrow_account.owner.eq(program_id) {
+            msg!("Mutual Escrow: Incorrect Mutual Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mutual_escrow_state =
+            MutualEscrowState::try_from_slice(&mutual_escrow_account.data.borrow())
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+        if mutual_escrow_state.account_type != AccountType::MutualEscrow {
+            msg!("Mutual Escrow: Passed account is not a Mutual Escrow");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(mutual_escrow_state)
+    }
+
+    // Checks the passed vaults and their authority against the mutual escrow state
+    fn validate_mutual_escrow_vaults(
+        program_id: &Pubkey,
+        mutual_escrow_state: &MutualEscrowState,
+        mutual_escrow_account: &AccountInfo,
+        x_vault_account: &AccountInfo,
+        y_vault_account: &AccountInfo,
+        vault_authority_account: &AccountInfo,
+    ) -> ProgramResult {
+        if !mutual_escrow_state.x_vault_pubkey.eq(x_vault_account.key)
+            || !mutual_escrow_state.y_vault_pubkey.eq(y_vault_account.key)
+        {
+            msg!("Mutual Escrow: Passed Vaults mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let checking_pda_address = Pubkey::create_program_address(
+            &[
+                b"mutual",
+                &mutual_escrow_account.key.to_bytes(),
+                &[mutual_escrow_state.vault_authority_bump],
+            ],
+            program_id,
+        )?;
+        if !checking_pda_address.eq(vault_authority_account.key) {
+            msg!("Mutual Escrow: PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    // Reads a call option state, making sure the passed holder mint and PDA are the option's
+    fn unpack_call_option_state(
+        program_id: &Pubkey,
+        call_option_account: &AccountInfo,
+        holder_mint_account: &AccountInfo,
+        vault_authority_account: &AccountInfo,
+    ) -> Result<CallOptionState, ProgramError> {
+        if !call_option_account.owner.eq(program_id) {
+            msg!("Call Option: Incorrect Call Option State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let call_option_state = CallOptionState::try_from_slice(&call_option_account.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if call_option_state.account_type != AccountType::CallOption {
+            msg!("Call Option: Passed account is not a Call Option");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !call_option_state
+            .holder_mint_pubkey
+            .eq(holder_mint_account.key)
+        {
+            msg!("Call Option: Passed Holder Mint mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let checking_pda_address = Pubkey::create_program_address(
+            &[
+                b"option",
+                &call_option_account.key.to_bytes(),
+                &[call_option_state.vault_authority_bump],
+            ],
+            program_id,
+        )?;
+        if !checking_pda_address.eq(vault_authority_account.key) {
+            msg!("Call Option: PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(call_option_state)
+    }
+
+    // Reads a loan state owned by this program
+    fn unpack_loan_state(
+        program_id: &Pubkey,
+        loan_account: &AccountInfo,
+    ) -> Result<LoanState, ProgramError> {
+        if !loan_account.owner.eq(program_id) {
+            msg!("Loan: Incorrect Loan State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let loan_state = LoanState::try_from_slice(&loan_account.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if loan_state.account_type != AccountType::Loan {
+            msg!("Loan: Passed account is not a Loan");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(loan_state)
+    }
+
+    // Checks the passed collateral vault and its authority against the loan state
+    fn validate_loan_vault(
+        program_id: &Pubkey,
+        loan_state: &LoanState,
+        loan_account: &AccountInfo,
+        collateral_vault_account: &AccountInfo,
+        vault_authority_account: &AccountInfo,
+    ) -> ProgramResult {
+        if !loan_state
+            .collateral_vault_pubkey
+            .eq(collateral_vault_account.key)
+        {
+            msg!("Loan: Passed Collateral Vault mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let checking_pda_address = Pubkey::create_program_address(
+            &[
+                b"loan",
+                &loan_account.key.to_bytes(),
+                &[loan_state.vault_authority_bump],
+            ],
+            program_id,
+        )?;
+        if !checking_pda_address.eq(vault_authority_account.key) {
+            msg!("Loan: PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    // Empties the collateral vault into the destination, then closes the vault and the loan to the borrower
+    fn release_loan_collateral<'a>(
+        loan_state: &LoanState,
+        loan_account: &AccountInfo<'a>,
+        collateral_vault_account: &AccountInfo<'a>,
+        destination_x_token_account: &AccountInfo<'a>,
+        borrower_account: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        vault_authority_account: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"loan",
+            &loan_account.key.to_bytes(),
+            &[loan_state.vault_authority_bump],
+        ];
+
+        let collateral_vault_state = TokenState::unpack(&collateral_vault_account.data.borrow())?;
+        Self::transfer_tokens(
+            token_program,
+            collateral_vault_account,
+            destination_x_token_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+            collateral_vault_state.amount,
+        )?;
+
+        msg!("Closing the Collateral Vault");
+        Self::close_vault(
+            token_program,
+            collateral_vault_account,
+            borrower_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+        )?;
+
+        msg!("Closing the Loan State Account");
+        Self::close_program_account(loan_account, borrower_account)
+    }
+
+    // Initialize collection bid processor
+    fn process_initialize_collection_bid(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bid_amount: u64,
+        criteria: CollectionCriteria,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let buyer_account = next_account_info(accounts_iterable)?;
+        let buyer_y_token_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let collection_bid_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the buyer has signed the transaction
+        if !buyer_account.is_signer {
+            msg!("Collection Bid Initialize: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed state account is owned by this program
+        if !collection_bid_account.owner.eq(program_id) {
+            msg!("Collection Bid Initialize: Incorrect Collection Bid State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Checking if the state account is rent exempt
+        let rent = Rent::get()?;
+        if !rent.is_exempt(
+            collection_bid_account.lamports(),
+            collection_bid_account.data_len(),
+        ) {
+            msg!("Collection Bid Initialize: State account is not rent exempt");
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        // Checking if the state has already been initialized (only empty accounts can be initialized)
+        let mut collection_bid_state =
+            CollectionBidState::try_from_slice(&collection_bid_account.data.borrow())
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+        if collection_bid_state.account_type != AccountType::Uninitialized {
+            msg!("Collection Bid Initialize: Account already initialized");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        if bid_amount == 0 {
+            msg!("Collection Bid Initialize: Bid has to be positive");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let buyer_y_token_account_state = TokenState::unpack(&buyer_y_token_account.data.borrow())?;
+        Self::validate_empty_vault(
+            vault_account,
+            buyer_account.key,
+            &buyer_y_token_account_state.mint,
+        )?;
+
+        let (vault_authority_address, vault_authority_bump) = Pubkey::find_program_address(
+            &[b"collection_bid", &collection_bid_account.key.to_bytes()],
+            program_id,
+        );
+
+        // BUSINESS LOGIC STARTS
+        collection_bid_state.account_type = AccountType::CollectionBid;
+        collection_bid_state.buyer_pubkey = *buyer_account.key;
+        collection_bid_state.vault_pubkey = *vault_account.key;
+        collection_bid_state.vault_authority_bump = vault_authority_bump;
+        collection_bid_state.bid_amount = bid_amount;
+        collection_bid_state.criteria = criteria;
+        collection_bid_state.serialize(&mut (&mut collection_bid_account.data.borrow_mut()[..]))?;
+
+        msg!("Transferring the bid to the Vault");
+        Self::transfer_tokens(
+            token_program,
+            buyer_y_token_account,
+            vault_account,
+            buyer_account,
+            &[],
+            bid_amount,
+        )?;
+
+        msg!("Transferring Vault ownership to the PDA");
+        Self::transfer_token_account_ownership(
+            token_program,
+            vault_account,
+            buyer_account,
+            &vault_authority_address,
+        )
+    }
+
+    // Fill collection bid processor
+    fn process_fill_collection_bid(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        merkle_proof: &[[u8; 32]],
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let seller_account = next_account_info(accounts_iterable)?;
+        let seller_nft_token_account = next_account_info(accounts_iterable)?;
+        let seller_y_token_account = next_account_info(accounts_iterable)?;
+        let buyer_nft_token_account = next_account_info(accounts_iterable)?;
+        let collection_bid_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let buyer_account = next_account_info(accounts_iterable)?;
+        let nft_mint_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the seller has signed the transaction
+        if !seller_account.is_signer {
+            msg!("Collection Bid Fill: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let collection_bid_state = Self::unpack_collection_bid_state(
+            program_id,
+            collection_bid_account,
+            vault_account,
+            buyer_account,
+            vault_authority_account,
+        )?;
+
+        // Checking if the seller holds an NFT of the passed mint
+        Self::validate_nft_mint(nft_mint_account)?;
+
+        let seller_nft_token_account_state =
+            TokenState::unpack(&seller_nft_token_account.data.borrow())?;
+        if !seller_nft_token_account_state.mint.eq(nft_mint_account.key)
+            || seller_nft_token_account_state.amount != 1
+        {
+            msg!("Collection Bid Fill: Seller does not hold the NFT");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the NFT belongs to the collection
+        let is_collection_member = match &collection_bid_state.criteria {
+            CollectionCriteria::MerkleRoot(merkle_root) => {
+                CollectionCriteria::merkle_root_of(nft_mint_account.key, merkle_proof)
+                    == *merkle_root
+            }
+            CollectionCriteria::VerifiedCollection(collection_pubkey) => {
+                let nft_metadata_account = next_account_info(accounts_iterable)?;
+                metadata::metadata_of(nft_metadata_account, nft_mint_account.key)?
+                    .is_verified_in(collection_pubkey)
+            }
+        };
+
+        if !is_collection_member {
+            msg!("Collection Bid Fill: NFT is not a member of the collection");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Self::prepare_associated_token_account(
+            seller_account,
+            buyer_account.key,
+            buyer_nft_token_account,
+            nft_mint_account.key,
+            token_program,
+            accounts_iterable,
+        )?;
+
+        // BUSINESS LOGIC STARTS
+        msg!("Transferring the NFT to the buyer");
+        Self::transfer_tokens(
+            token_program,
+            seller_nft_token_account,
+            buyer_nft_token_account,
+            seller_account,
+            &[],
+            1,
+        )?;
+
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"collection_bid",
+            &collection_bid_account.key.to_bytes(),
+            &[collection_bid_state.vault_authority_bump],
+        ];
+
+        msg!("Transferring the bid to the seller");
+        Self::transfer_tokens(
+            token_program,
+            vault_account,
+            seller_y_token_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+            collection_bid_state.bid_amount,
+        )?;
+
+        // Anything else sent to the vault goes to the seller along with the bid
+        msg!("Closing the Vault");
+        Self::sweep_and_close_vault(
+            token_program,
+            vault_account,
+            seller_y_token_account,
+            buyer_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+        )?;
+
+        msg!("Closing the Collection Bid State Account");
+        Self::close_program_account(collection_bid_account, buyer_account)
+    }
+
+    // Cancel collection bid processor
+    fn process_cancel_collection_bid(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let buyer_account = next_account_info(accounts_iterable)?;
+        let collection_bid_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let buyer_y_token_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the buyer has signed the transaction
+        if !buyer_account.is_signer {
+            msg!("Collection Bid Cancel: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let collection_bid_state = Self::unpack_collection_bid_state(
+            program_id,
+            collection_bid_account,
+            vault_account,
+            buyer_account,
+            vault_authority_account,
+        )?;
+
+        // BUSINESS LOGIC STARTS
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"collection_bid",
+            &collection_bid_account.key.to_bytes(),
+            &[collection_bid_state.vault_authority_bump],
+        ];
+
+        // Whatever the vault holds goes back, the bid along with anything else sent to it
+        msg!("Returning the bid to the buyer and closing the Vault");
+        Self::sweep_and_close_vault(
+            token_program,
+            vault_account,
+            buyer_y_token_account,
+            buyer_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+        )?;
+
+        msg!("Closing the Collection Bid State Account");
+        Self::close_program_account(collection_bid_account, buyer_account)
+    }
+
+    // Initialize mutual escrow processor
+    fn process_initialize_mutual_escrow(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        x_token_amount: u64,
+        y_token_amount: u64,
+        taker_pubkey: Pubkey,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let maker_account = next_account_info(accounts_iterable)?;
+        let x_vault_account = next_account_info(accounts_iterable)?;
+        let y_vault_account = next_account_info(accounts_iterable)?;
+        let mutual_escrow_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the maker has signed the transaction
+        if !maker_account.is_signer {
+            msg!("Mutual Escrow Initialize: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed state account is owned by this program
+        if !mutual_escrow_account.owner.eq(program_id) {
+            msg!("Mutual Escrow Initialize: Incorrect Mutual Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Checking if the state account is rent exempt
+        let rent = Rent::get()?;
+        if !rent.is_exempt(
+            mutual_escrow_account.lamports(),
+            mutual_escrow_account.data_len(),
+        ) {
+            msg!("Mutual Escrow Initialize: State account is not rent exempt");
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        // Checking if the state has already been initialized (only empty accounts can be initialized)
+        let mut mutual_escrow_state =
+            MutualEscrowState::try_from_slice(&mutual_escrow_account.data.borrow())
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+        if mutual_escrow_state.account_type != AccountType::Uninitialized {
+            msg!("Mutual Escrow Initialize: Account already initialized");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        if x_token_amount == 0 || y_token_amount == 0 {
+            msg!("Mutual Escrow Initialize: Both legs have to be positive");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if taker_pubkey == Pubkey::default() || taker_pubkey.eq(maker_account.key) {
+            msg!("Mutual Escrow Initialize: Taker has to be someone other than the maker");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // The vaults set the mints of the legs
+        for vault_account in [x_vault_account, y_vault_account] {
+            let vault_state = TokenState::unpack(&vault_account.data.borrow())?;
+            Self::validate_empty_vault(vault_account, maker_account.key, &vault_state.mint)?;
+        }
+
+        let (vault_authority_address, vault_authority_bump) = Pubkey::find_program_address(
+            &[b"mutual", &mutual_escrow_account.key.to_bytes()],
+            program_id,
+        );
+
+        // BUSINESS LOGIC STARTS
+        mutual_escrow_state.account_type = AccountType::MutualEscrow;
+        mutual_escrow_state.maker_pubkey = *maker_account.key;
+        mutual_escrow_state.taker_pubkey = taker_pubkey;
+        mutual_escrow_state.x_vault_pubkey = *x_vault_account.key;
+        mutual_escrow_state.y_vault_pubkey = *y_vault_account.key;
+        mutual_escrow_state.vault_authority_bump = vault_authority_bump;
+        mutual_escrow_state.x_token_amount = x_token_amount;
+        mutual_escrow_state.y_token_amount = y_token_amount;
+        mutual_escrow_state.is_x_deposited = false;
+        mutual_escrow_state.is_y_deposited = false;
+        mutual_escrow_state.serialize(&mut (&mut mutual_escrow_account.data.borrow_mut()[..]))?;
+
+        msg!("Transferring Vault ownerships to the PDA");
+        for vault_account in [x_vault_account, y_vault_account] {
+            Self::transfer_token_account_ownership(
+                token_program,
+                vault_account,
+                maker_account,
+                &vault_authority_address,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Deposit mutual escrow processor
+    fn process_deposit_mutual_escrow(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let party_account = next_account_info(accounts_iterable)?;
+        let party_token_account = next_account_info(accounts_iterable)?;
+        let mutual_escrow_account = next_account_info(accounts_iterable)?;
+        let vault_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the party has signed the transaction
+        if !party_account.is_signer {
+            msg!("Mutual Escrow Deposit: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut mutual_escrow_state =
+            Self::unpack_mutual_escrow_state(program_id, mutual_escrow_account)?;
+
+        // Picking the caller's leg
+        let (vault_pubkey, amount, is_deposited) =
+            if mutual_escrow_state.maker_pubkey.eq(party_account.key) {
+                (
+                    mutual_escrow_state.x_vault_pubkey,
+                    mutual_escrow_state.x_token_amount,
+                    &mut mutual_escrow_state.is_x_deposited,
+                )
+            } else if mutual_escrow_state.taker_pubkey.eq(party_account.key) {
+                (
+                    mutual_escrow_state.y_vault_pubkey,
+                    mutual_escrow_state.y_token_amount,
+                    &mut mutual_escrow_state.is_y_deposited,
+                )
+            } else {
+                msg!("Mutual Escrow Deposit: Caller is neither the maker nor the taker");
+                return Err(ProgramError::InvalidAccountData);
+            };
+
+        if *is_deposited {
+            msg!("Mutual Escrow Deposit: Caller's leg has already been deposited");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !vault_pubkey.eq(vault_account.key) {
+            msg!("Mutual Escrow Deposit: Passed Vault mismatch with the caller's leg");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the deposit is going to the PDA's custody rather than staying with the other party
+        let vault_authority_address = Pubkey::create_program_address(
+            &[
+                b"mutual",
+                &mutual_escrow_account.key.to_bytes(),
+                &[mutual_escrow_state.vault_authority_bump],
+            ],
+            program_id,
+        )?;
+        let vault_state = TokenState::unpack(&vault_account.data.borrow())?;
+        if !vault_state.owner.eq(&vault_authority_address) {
+            msg!("Mutual Escrow Deposit: Vault is not owned by the PDA");
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        // BUSINESS LOGIC STARTS
+        *is_deposited = true;
+        mutual_escrow_state.serialize(&mut (&mut mutual_escrow_account.data.borrow_mut()[..]))?;
+
+        msg!("Depositing {} tokens into the Vault", amount);
+        Self::transfer_tokens(
+            token_program,
+            party_token_account,
+            vault_account,
+            party_account,
+            &[],
+            amount,
+        )
+    }
+
+    // Withdraw mutual deposit processor
+    fn process_withdraw_mutual_deposit(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let party_account = next_account_info(accounts_iterable)?;
+        let mutual_escrow_account = next_account_info(accounts_iterable)?;
+        let x_vault_account = next_account_info(accounts_iterable)?;
+        let y_vault_account = next_account_info(accounts_iterable)?;
+        let party_token_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the party has signed the transaction
+        if !party_account.is_signer {
+            msg!("Mutual Escrow Withdraw: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut mutual_escrow_state =
+            Self::unpack_mutual_escrow_state(program_id, mutual_escrow_account)?;
+        Self::validate_mutual_escrow_vaults(
+            program_id,
+            &mutual_escrow_state,
+            mutual_escrow_account,
+            x_vault_account,
+            y_vault_account,
+            vault_authority_account,
+        )?;
+
+        // Checking if the legs can still be withdrawn
+        if mutual_escrow_state.is_funded() {
+            msg!("Mutual Escrow Withdraw: Both legs are deposited, the escrow can only be settled");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let is_maker = mutual_escrow_state.maker_pubkey.eq(party_account.key);
+        let (vault_account, is_deposited) = if is_maker {
+            (x_vault_account, &mut mutual_escrow_state.is_x_deposited)
+        } else if mutual_escrow_state.taker_pubkey.eq(party_account.key) {
+            (y_vault_account, &mut mutual_escrow_state.is_y_deposited)
+        } else {
+            msg!("Mutual Escrow Withdraw: Caller is neither the maker nor the taker");
+            return Err(ProgramError::InvalidAccountData);
+        };
+
+        // Only the maker can withdraw without a deposit, to close the escrow
+        if !*is_deposited && !is_maker {
+            msg!("Mutual Escrow Withdraw: Caller has nothing deposited");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"mutual",
+            &mutual_escrow_account.key.to_bytes(),
+            &[mutual_escrow_state.vault_authority_bump],
+        ];
+
+        if *is_deposited {
+            *is_deposited = false;
+
+            let vault_state = TokenState::unpack(&vault_account.data.borrow())?;
+            msg!("Returning the deposit of {}", vault_state.amount);
+            Self::transfer_tokens(
+                token_program,
+                vault_account,
+                party_token_account,
+                vault_authority_account,
+                &[vault_authority_seed_bump_combination],
+                vault_state.amount,
+            )?;
+        }
+
+        if !is_maker || mutual_escrow_state.is_y_deposited {
+            mutual_escrow_state
+                .serialize(&mut (&mut mutual_escrow_account.data.borrow_mut()[..]))?;
+            return Ok(());
+        }
+
+        // Whatever anyone sent to the vaults goes to the maker, the Y leftovers into the maker's associated token account
+        let maker_y_token_account = next_account_info(accounts_iterable)?;
+        let y_vault_state = TokenState::unpack(&y_vault_account.data.borrow())?;
+        Self::prepare_associated_token_account(
+            party_account,
+            party_account.key,
+            maker_y_token_account,
+            &y_vault_state.mint,
+            token_program,
+            accounts_iterable,
+        )?;
+
+        msg!("Closing the Vaults");
+        Self::sweep_and_close_vault(
+            token_program,
+            x_vault_account,
+            party_token_account,
+            party_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+        )?;
+        Self::sweep_and_close_vault(
+            token_program,
+            y_vault_account,
+            maker_y_token_account,
+            party_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+        )?;
+
+        msg!("Closing the Mutual Escrow State Account");
+        Self::close_program_account(mutual_escrow_account, party_account)
+    }
+
+    // Settle mutual escrow processor
+    fn process_settle_mutual_escrow(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let party_account = next_account_info(accounts_iterable)?;
+        let mutual_escrow_account = next_account_info(accounts_iterable)?;
+        let x_vault_account = next_account_info(accounts_iterable)?;
+        let y_vault_account = next_account_info(accounts_iterable)?;
+        let taker_x_token_account = next_account_info(accounts_iterable)?;
+        let maker_y_token_account = next_account_info(accounts_iterable)?;
+        let maker_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the party has signed the transaction
+        if !party_account.is_signer {
+            msg!("Mutual Escrow Settle: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mutual_escrow_state =
+            Self::unpack_mutual_escrow_state(program_id, mutual_escrow_account)?;
+        Self::validate_mutual_escrow_vaults(
+            program_id,
+            &mutual_escrow_state,
+            mutual_escrow_account,
+            x_vault_account,
+            y_vault_account,
+            vault_authority_account,
+        )?;
+
+        // Checking if the caller is one of the parties
+        if !mutual_escrow_state.maker_pubkey.eq(party_account.key)
+            && !mutual_escrow_state.taker_pubkey.eq(party_account.key)
+        {
+            msg!("Mutual Escrow Settle: Caller is neither the maker nor the taker");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !mutual_escrow_state.maker_pubkey.eq(maker_account.key) {
+            msg!("Mutual Escrow Settle: Passed maker mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if both legs are deposited
+        if !mutual_escrow_state.is_funded() {
+            msg!("Mutual Escrow Settle: Both legs have to be deposited first");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Making sure both parties can receive their legs (creating their associated token accounts if needed)
+        let x_vault_state = TokenState::unpack(&x_vault_account.data.borrow())?;
+        let y_vault_state = TokenState::unpack(&y_vault_account.data.borrow())?;
+        Self::prepare_associated_token_account(
+            party_account,
+            &mutual_escrow_state.taker_pubkey,
+            taker_x_token_account,
+            &x_vault_state.mint,
+            token_program,
+            accounts_iterable,
+        )?;
+        Self::prepare_associated_token_account(
+            party_account,
+            &mutual_escrow_state.maker_pubkey,
+            maker_y_token_account,
+            &y_vault_state.mint,
+            token_program,
+            accounts_iterable,
+        )?;
+
+        // BUSINESS LOGIC STARTS
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"mutual",
+            &mutual_escrow_account.key.to_bytes(),
+            &[mutual_escrow_state.vault_authority_bump],
+        ];
+
+        msg!("Transferring the X leg to the taker");
+        Self::transfer_tokens(
+            token_program,
+            x_vault_account,
+            taker_x_token_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+            x_vault_state.amount,
+        )?;
+
+        msg!("Transferring the Y leg to the maker");
+        Self::transfer_tokens(
+            token_program,
+            y_vault_account,
+            maker_y_token_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+            y_vault_state.amount,
+        )?;
+
+        msg!("Closing the Vaults");
+        for vault_account in [x_vault_account, y_vault_account] {
+            Self::close_vault(
+                token_program,
+                vault_account,
+                maker_account,
+                vault_authority_account,
+                &[vault_authority_seed_bump_combination],
+            )?;
+        }
+
+        msg!("Closing the Mutual Escrow State Account");
+        Self::close_program_account(mutual_escrow_account, maker_account)
+    }
+
+    // Write call option processor
+    fn process_write_call_option(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        x_token_amount: u64,
+        premium_amount: u64,
+        strike_amount: u64,
+        expiry_timestamp: i64,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let writer_account = next_account_info(accounts_iterable)?;
+        let writer_x_token_account = next_account_info(accounts_iterable)?;
+        let y_token_mint_account = next_account_info(accounts_iterable)?;
+        let x_vault_account = next_account_info(accounts_iterable)?;
+        let holder_mint_account = next_account_info(accounts_iterable)?;
+        let call_option_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the writer has signed the transaction
+        if !writer_account.is_signer {
+            msg!("Call Option Write: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed state account is owned by this program
+        if !call_option_account.owner.eq(program_id) {
+            msg!("Call Option Write: Incorrect Call Option State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Checking if the state account is rent exempt
+        let rent = Rent::get()?;
+        if !rent.is_exempt(
+            call_option_account.lamports(),
+            call_option_account.data_len(),
+        ) {
+            msg!("Call Option Write: State account is not rent exempt");
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        // Checking if the state has already been initialized (only empty accounts can be initialized)
+        let mut call_option_state =
+            CallOptionState::try_from_slice(&call_option_account.data.borrow())
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+        if call_option_state.account_type != AccountType::Uninitialized {
+            msg!("Call Option Write: Account already initialized");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // Checking if the option terms are valid
+        let clock = Clock::get()?;
+        if x_token_amount == 0 || strike_amount == 0 || expiry_timestamp <= clock.unix_timestamp {
+            msg!(
+                "Call Option Write: X and strike have to be positive and the expiry in the future"
+            );
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if !spl_token::check_id(y_token_mint_account.owner) {
+            msg!("Call Option Write: Y Token Mint is not a token mint");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        MintState::unpack(&y_token_mint_account.data.borrow())?;
+
+        let writer_x_token_account_state =
+            TokenState::unpack(&writer_x_token_account.data.borrow())?;
+        Self::validate_empty_vault(
+            x_vault_account,
+            writer_account.key,
+            &writer_x_token_account_state.mint,
+        )?;
+        Self::validate_empty_mint(holder_mint_account, writer_account.key)?;
+
+        let (vault_authority_address, vault_authority_bump) = Pubkey::find_program_address(
+            &[b"option", &call_option_account.key.to_bytes()],
+            program_id,
+        );
+
+        // BUSINESS LOGIC STARTS
+        call_option_state.account_type = AccountType::CallOption;
+        call_option_state.writer_pubkey = *writer_account.key;
+        call_option_state.y_token_mint_pubkey = *y_token_mint_account.key;
+        call_option_state.x_vault_pubkey = *x_vault_account.key;
+        call_option_state.holder_mint_pubkey = *holder_mint_account.key;
+        call_option_state.vault_authority_bump = vault_authority_bump;
+        call_option_state.premium_amount = premium_amount;
+        call_option_state.strike_amount = strike_amount;
+        call_option_state.expiry_timestamp = expiry_timestamp;
+        call_option_state.is_sold = false;
+        call_option_state.serialize(&mut (&mut call_option_account.data.borrow_mut()[..]))?;
+
+        msg!("Transferring the covered X to the Vault");
+        Self::transfer_tokens(
+            token_program,
+            writer_x_token_account,
+            x_vault_account,
+            writer_account,
+            &[],
+            x_token_amount,
+        )?;
+
+        msg!("Transferring Vault ownership and Holder Mint authority to the PDA");
+        Self::transfer_token_account_ownership(
+            token_program,
+            x_vault_account,
+            writer_account,
+            &vault_authority_address,
+        )?;
+        Self::transfer_mint_authority(
+            token_program,
+            holder_mint_account,
+            writer_account,
+            Some(&vault_authority_address),
+        )
+    }
+
+    // Buy call option processor
+    fn process_buy_call_option(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let buyer_account = next_account_info(accounts_iterable)?;
+        let buyer_y_token_account = next_account_info(accounts_iterable)?;
+        let writer_y_token_account = next_account_info(accounts_iterable)?;
+        let buyer_holder_token_account = next_account_info(accounts_iterable)?;
+        let call_option_account = next_account_info(accounts_iterable)?;
+        let holder_mint_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the buyer has signed the transaction
+        if !buyer_account.is_signer {
+            msg!("Call Option Buy: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut call_option_state = Self::unpack_call_option_state(
+            program_id,
+            call_option_account,
+            holder_mint_account,
+            vault_authority_account,
+        )?;
+
+        // Checking if the option is still for sale
+        let clock = Clock::get()?;
+        if call_option_state.is_sold || clock.unix_timestamp >= call_option_state.expiry_timestamp {
+            msg!("Call Option Buy: Option is sold or expired");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Self::prepare_associated_token_account(
+            buyer_account,
+            buyer_account.key,
+            buyer_holder_token_account,
+            holder_mint_account.key,
+            token_program,
+            accounts_iterable,
+        )?;
+
+        // The premium goes to the writer's associated token account, which the writer can't get rid of for good
+        Self::prepare_associated_token_account(
+            buyer_account,
+            &call_option_state.writer_pubkey,
+            writer_y_token_account,
+            &call_option_state.y_token_mint_pubkey,
+            token_program,
+            accounts_iterable,
+        )?;
+
+        // BUSINESS LOGIC STARTS
+        call_option_state.is_sold = true;
+        call_option_state.serialize(&mut (&mut call_option_account.data.borrow_mut()[..]))?;
+
+        if call_option_state.premium_amount > 0 {
+            msg!("Paying the premium of {}", call_option_state.premium_amount);
+            Self::transfer_tokens(
+                token_program,
+                buyer_y_token_account,
+                writer_y_token_account,
+                buyer_account,
+                &[],
+                call_option_state.premium_amount,
+            )?;
+        }
+
+        msg!("Minting the Holder Token to the buyer");
+        Self::mint_tokens(
+            token_program,
+            holder_mint_account,
+            buyer_holder_token_account,
+            vault_authority_account,
+            &[&[
+                b"option",
+                &call_option_account.key.to_bytes(),
+                &[call_option_state.vault_authority_bump],
+            ]],
+            1,
+        )
+    }
+
+    // Exercise call option processor
+    fn process_exercise_call_option(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let holder_account = next_account_info(accounts_iterable)?;
+        let holder_token_account = next_account_info(accounts_iterable)?;
+        let holder_y_token_account = next_account_info(accounts_iterable)?;
+        let holder_x_token_account = next_account_info(accounts_iterable)?;
+        let writer_y_token_account = next_account_info(accounts_iterable)?;
+        let call_option_account = next_account_info(accounts_iterable)?;
+        let x_vault_account = next_account_info(accounts_iterable)?;
+        let holder_mint_account = next_account_info(accounts_iterable)?;
+        let writer_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the holder has signed the transaction
+        if !holder_account.is_signer {
+            msg!("Call Option Exercise: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let call_option_state = Self::unpack_call_option_state(
+            program_id,
+            call_option_account,
+            holder_mint_account,
+            vault_authority_account,
+        )?;
+
+        if !call_option_state.x_vault_pubkey.eq(x_vault_account.key)
+            || !call_option_state.writer_pubkey.eq(writer_account.key)
+        {
+            msg!("Call Option Exercise: Passed accounts mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the option can still be exercised
+        let clock = Clock::get()?;
+        if !call_option_state.is_sold || clock.unix_timestamp >= call_option_state.expiry_timestamp
+        {
+            msg!("Call Option Exercise: Option is unsold or expired");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // The strike goes to the writer's associated token account, recreated if the writer closed it
+        Self::prepare_associated_token_account(
+            holder_account,
+            &call_option_state.writer_pubkey,
+            writer_y_token_account,
+            &call_option_state.y_token_mint_pubkey,
+            token_program,
+            accounts_iterable,
+        )?;
+
+        // BUSINESS LOGIC STARTS
+        // The holder token gets burnt, which fails unless the caller holds it
+        msg!("Burning the Holder Token");
+        Self::burn_tokens(
+            token_program,
+            holder_token_account,
+            holder_mint_account,
+            holder_account,
+            1,
+        )?;
+
+        msg!("Paying the strike of {}", call_option_state.strike_amount);
+        Self::transfer_tokens(
+            token_program,
+            holder_y_token_account,
+            writer_y_token_account,
+            holder_account,
+            &[],
+            call_option_state.strike_amount,
+        )?;
+
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"option",
+            &call_option_account.key.to_bytes(),
+            &[call_option_state.vault_authority_bump],
+        ];
+
+        let x_vault_state = TokenState::unpack(&x_vault_account.data.borrow())?;
+        msg!("Transferring the covered X to the holder");
+        Self::transfer_tokens(
+            token_program,
+            x_vault_account,
+            holder_x_token_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+            x_vault_state.amount,
+        )?;
+
+        msg!("Closing the Vault");
+        Self::close_vault(
+            token_program,
+            x_vault_account,
+            writer_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+        )?;
+
+        msg!("Closing the Call Option State Account");
+        Self::close_program_account(call_option_account, writer_account)
+    }
+
+    // Reclaim call option processor
+    fn process_reclaim_call_option(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let writer_account = next_account_info(accounts_iterable)?;
+        let call_option_account = next_account_info(accounts_iterable)?;
+        let x_vault_account = next_account_info(accounts_iterable)?;
+        let writer_x_token_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the writer has signed the transaction
+        if !writer_account.is_signer {
+            msg!("Call Option Reclaim: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if !call_option_account.owner.eq(program_id) {
+            msg!("Call Option Reclaim: Incorrect Call Option State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let call_option_state = CallOptionState::try_from_slice(&call_option_account.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if call_option_state.account_type != AccountType::CallOption {
+            msg!("Call Option Reclaim: Passed account is not a Call Option");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !call_option_state.writer_pubkey.eq(writer_account.key)
+            || !call_option_state.x_vault_pubkey.eq(x_vault_account.key)
+        {
+            msg!("Call Option Reclaim: Passed accounts mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let vault_authority_seed_bump_combination: &[&[u8]] = &[
+            b"option",
+            &call_option_account.key.to_bytes(),
+            &[call_option_state.vault_authority_bump],
+        ];
+        let checking_pda_address =
+            Pubkey::create_program_address(vault_authority_seed_bump_combination, program_id)?;
+        if !checking_pda_address.eq(vault_authority_account.key) {
+            msg!("Call Option Reclaim: PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the holder can no longer exercise
+        let clock = Clock::get()?;
+        if call_option_state.is_sold && clock.unix_timestamp < call_option_state.expiry_timestamp {
+            msg!("Call Option Reclaim: Sold option has not expired yet");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let x_vault_state = TokenState::unpack(&x_vault_account.data.borrow())?;
+        msg!("Returning the covered X to the writer");
+        Self::transfer_tokens(
+            token_program,
+            x_vault_account,
+            writer_x_token_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+            x_vault_state.amount,
+        )?;
+
+        msg!("Closing the Vault");
+        Self::close_vault(
+            token_program,
+            x_vault_account,
+            writer_account,
+            vault_authority_account,
+            &[vault_authority_seed_bump_combination],
+        )?;
+
+        msg!("Closing the Call Option State Account");
+        Self::close_program_account(call_option_account, writer_account)
+    }
+
+    // Transfer escrow ownership processor
+    fn process_transfer_escrow_ownership(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let alice_account = next_account_info(accounts_iterable)?;
+        let new_maker_account = next_account_info(accounts_iterable)?;
+        let alice_y_token_account = next_account_info(accounts_iterable)?;
+        let new_maker_y_token_account = next_account_info(accounts_iterable)?;
+        let new_rent_refund_account = next_account_info(accounts_iterable)?;
+        let escrow_account = next_account_info(accounts_iterable)?;
+        let pda_temp_x_token_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let escrow_pda_account = next_account_info(accounts_iterable)?;
+
+        // Checking if Alice has signed the transaction
+        if !alice_account.is_signer {
+            msg!("Escrow Transfer Ownership: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed escrow state account is owned by this program
+        if !escrow_account.owner.eq(program_id) {
+            msg!("Escrow Transfer Ownership: Incorrect Escrow State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut escrow_account_state =
+            EscrowState::unpack_from_slice(&escrow_account.data.borrow())?;
+
+        if !escrow_account_state.alice_pubkey.eq(alice_account.key)
+            || !escrow_account_state
+                .alice_y_token_pubkey
+                .eq(alice_y_token_account.key)
+            || !escrow_account_state
+                .alice_temp_x_token_pubkey
+                .eq(pda_temp_x_token_account.key)
+        {
+            msg!("Escrow Transfer Ownership: Passed accounts mismatch with Escrow state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let pda_seed_bump_combination: &[&[u8]] = &[
+            b"escrow",
+            &alice_account.key.to_bytes(),
+            &[escrow_account_state.escrow_pda_bump],
+        ];
+        let checking_pda_address =
+            Pubkey::create_program_address(pda_seed_bump_combination, program_id)?;
+        if !checking_pda_address.eq(escrow_pda_account.key) {
+            msg!("Escrow Transfer Ownership: Escrow PDA mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Delegations live on Alice's own X token account, and receipt escrows change hands with the receipt
+        if escrow_account_state.custody != XCustody::Vault || escrow_account_state.receipt.is_some()
+        {
+            msg!("Escrow Transfer Ownership: Delegated and receipt escrows can't change makers");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if new_maker_account.key.eq(alice_account.key)
+            || escrow_account_state
+                .arbitration
+                .as_ref()
+                .is_some_and(|arbitration| {
+                    arbitration.counterparty_pubkey.eq(new_maker_account.key)
+                })
+        {
+            msg!("Escrow Transfer Ownership: New maker has to be someone other than Alice and the counterparty");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // The proceeds have to stay in the same Y token
+        if !spl_token::check_id(new_maker_y_token_account.owner) {
+            msg!("Escrow Transfer Ownership: New maker's Y account is not a token account");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let alice_y_token_account_state = TokenState::unpack(&alice_y_token_account.data.borrow())?;
+        let new_maker_y_token_account_state =
+            TokenState::unpack(&new_maker_y_token_account.data.borrow())?;
+        if !new_maker_y_token_account_state
+            .mint
+            .eq(&alice_y_token_account_state.mint)
+        {
+            msg!(
+                "Escrow Transfer Ownership: New maker's Y Token Account mint mismatch with Alice's"
+            );
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (new_escrow_pda_address, new_escrow_pda_bump) = Pubkey::find_program_address(
+            &[b"escrow", &new_maker_account.key.to_bytes()],
+            program_id,
+        );
+
+        // BUSINESS LOGIC STARTS
+        msg!("Moving the Temp X Token Account under the new maker's PDA");
+        Self::migrate_vault_authority(
+            token_program,
+            pda_temp_x_token_account,
+            escrow_pda_account,
+            pda_seed_bump_combination,
+            &new_escrow_pda_address,
+        )?;
+
+        match &mut escrow_account_state.mode {
+            EscrowMode::EnglishAuction(EnglishAuction {
+                bid_vault_pubkey, ..
+            })
+            | EscrowMode::SealedBidAuction(SealedBidAuction {
+                bid_vault_pubkey, ..
+            }) => {
+                let bid_vault_account = next_account_info(accounts_iterable)?;
+                if !(*bid_vault_pubkey).eq(bid_vault_account.key) {
+                    msg!(
+                        "Escrow Transfer Ownership: Passed Bid Vault mismatch with Escrow state's"
+                    );
+                    return Err(ProgramError::InvalidAccountData);
+                }
+
+                msg!("Moving the Bid Vault under the new maker's PDA");
+                Self::migrate_vault_authority(
+                    token_program,
+                    bid_vault_account,
+                    escrow_pda_account,
+                    pda_seed_bump_combination,
+                    &new_escrow_pda_address,
+                )?;
+            }
+            EscrowMode::Bundle(bundle) => {
+                for x_leg in bundle.x_legs.iter() {
+                    let x_vault_account = next_account_info(accounts_iterable)?;
+                    if !x_leg.token_pubkey.eq(x_vault_account.key) {
+                        msg!("Escrow Transfer Ownership: Passed X Vault mismatch with the bundle's X leg");
+                        return Err(ProgramError::InvalidAccountData);
+                    }
+
+                    msg!("Moving an X Vault under the new maker's PDA");
+                    Self::migrate_vault_authority(
+                        token_program,
+                        x_vault_account,
+                        escrow_pda_account,
+                        pda_seed_bump_combination,
+                        &new_escrow_pda_address,
+                    )?;
+                }
+
+                // The extra Y legs are proceeds as well, each staying in its token and going to the same wallet as the main leg
+                for y_leg in bundle.y_legs.iter_mut() {
+                    let y_leg_token_account = next_account_info(accounts_iterable)?;
+                    let new_maker_token_account = next_account_info(accounts_iterable)?;
+                    if !y_leg.token_pubkey.eq(y_leg_token_account.key) {
+                        msg!("Escrow Transfer Ownership: Passed Y leg Token Account mismatch with the bundle's Y leg");
+                        return Err(ProgramError::InvalidAccountData);
+                    }
+
+                    if !spl_token::check_id(new_maker_token_account.owner) {
+                        msg!("Escrow Transfer Ownership: New maker's Y leg account is not a token account");
+                        return Err(ProgramError::IncorrectProgramId);
+                    }
+
+                    let y_leg_token_account_state =
+                        TokenState::unpack(&y_leg_token_account.data.borrow())?;
+                    let new_maker_token_account_state =
+                        TokenState::unpack(&new_maker_token_account.data.borrow())?;
+                    if !new_maker_token_account_state
+                        .mint
+                        .eq(&y_leg_token_account_state.mint)
+                        || !new_maker_token_account_state
+                            .owner
+                            .eq(&new_maker_y_token_account_state.owner)
+                    {
+                        msg!("Escrow Transfer Ownership: New maker's Y leg Token Account has to be of the leg's mint and owned like the new maker's Y Token Account");
+                        return Err(ProgramError::InvalidAccountData);
+                    }
+
+                    y_leg.token_pubkey = *new_maker_token_account.key;
+                }
+            }
+            _ => {}
+        }
+
+        escrow_account_state.alice_pubkey = *new_maker_account.key;
+        escrow_account_state.alice_y_token_pubkey = *new_maker_y_token_account.key;
+        escrow_account_state.y_beneficiary_pubkey = new_maker_y_token_account_state.owner;
+        escrow_account_state.rent_refund_pubkey = *new_rent_refund_account.key;
+        escrow_account_state.escrow_pda_bump = new_escrow_pda_bump;
+        escrow_account_state.serialize(&mut (&mut escrow_account.data.borrow_mut()[..]))?;
+
+        msg!(
+            "Escrow now belongs to {}",
+            new_maker_account.key.to_string()
+        );
+        Ok(())
+    }
+
+    // Request loan processor
+    fn process_request_loan(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        collateral_amount: u64,
+        principal_amount: u64,
+        interest_amount: u64,
+        loan_duration: i64,
+    ) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let borrower_account = next_account_info(accounts_iterable)?;
+        let borrower_x_token_account = next_account_info(accounts_iterable)?;
+        let borrower_y_token_account = next_account_info(accounts_iterable)?;
+        let collateral_vault_account = next_account_info(accounts_iterable)?;
+        let loan_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+
+        // Checking if the borrower has signed the transaction
+        if !borrower_account.is_signer {
+            msg!("Loan Request: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Checking if passed state account is owned by this program
+        if !loan_account.owner.eq(program_id) {
+            msg!("Loan Request: Incorrect Loan State Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Checking if the state account is rent exempt
+        let rent = Rent::get()?;
+        if !rent.is_exempt(loan_account.lamports(), loan_account.data_len()) {
+            msg!("Loan Request: State account is not rent exempt");
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        // Checking if the state has already been initialized (only empty accounts can be initialized)
+        let mut loan_state = LoanState::try_from_slice(&loan_account.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if loan_state.account_type != AccountType::Uninitialized {
+            msg!("Loan Request: Account already initialized");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // Checking if the loan terms are valid
+        if collateral_amount == 0 || principal_amount == 0 || loan_duration <= 0 {
+            msg!("Loan Request: Collateral, principal and duration have to be positive");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if principal_amount.checked_add(interest_amount).is_none() {
+            msg!("Loan Request: Repayment amount overflows");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if !spl_token::check_id(borrower_y_token_account.owner) {
+            msg!("Loan Request: Borrower's Y account is not a token account");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let borrower_y_token_account_state =
+            TokenState::unpack(&borrower_y_token_account.data.borrow())?;
+
+        let borrower_x_token_account_state =
+            TokenState::unpack(&borrower_x_token_account.data.borrow())?;
+        Self::validate_empty_vault(
+            collateral_vault_account,
+            borrower_account.key,
+            &borrower_x_token_account_state.mint,
+        )?;
+
+        let (vault_authority_address, vault_authority_bump) =
+            Pubkey::find_program_address(&[b"loan", &loan_account.key.to_bytes()], program_id);
+
+        // BUSINESS LOGIC STARTS
+        loan_state.account_type = AccountType::Loan;
+        loan_state.borrower_pubkey = *borrower_account.key;
+        loan_state.borrower_y_token_pubkey = *borrower_y_token_account.key;
+        loan_state.lender_pubkey = Pubkey::default();
+        loan_state.y_token_mint_pubkey = borrower_y_token_account_state.mint;
+        loan_state.collateral_vault_pubkey = *collateral_vault_account.key;
+        loan_state.vault_authority_bump = vault_authority_bump;
+        loan_state.principal_amount = principal_amount;
+        loan_state.interest_amount = interest_amount;
+        loan_state.loan_duration = loan_duration;
+        loan_state.repayment_deadline = 0;
+        loan_state.serialize(&mut (&mut loan_account.data.borrow_mut()[..]))?;
+
+        msg!("Transferring the collateral to the Vault");
+        Self::transfer_tokens(
+            token_program,
+            borrower_x_token_account,
+            collateral_vault_account,
+            borrower_account,
+            &[],
+            collateral_amount,
+        )?;
+
+        msg!("Transferring Vault ownership to the PDA");
+        Self::transfer_token_account_ownership(
+            token_program,
+            collateral_vault_account,
+            borrower_account,
+            &vault_authority_address,
+        )
+    }
+
+    // Fund loan processor
+    fn process_fund_loan(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let lender_account = next_account_info(accounts_iterable)?;
+        let lender_y_token_account = next_account_info(accounts_iterable)?;
+        let borrower_y_token_account = next_account_info(accounts_iterable)?;
+        let loan_account = next_account_info(accounts_iterable)?;
+        let collateral_vault_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the lender has signed the transaction
+        if !lender_account.is_signer {
+            msg!("Loan Fund: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut loan_state = Self::unpack_loan_state(program_id, loan_account)?;
+        Self::validate_loan_vault(
+            program_id,
+            &loan_state,
+            loan_account,
+            collateral_vault_account,
+            vault_authority_account,
+        )?;
+
+        if !loan_state
+            .borrower_y_token_pubkey
+            .eq(borrower_y_token_account.key)
+        {
+            msg!("Loan Fund: Passed Borrower's Y Token Account mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the collateral is actually in the PDA's custody
+        let collateral_vault_state = TokenState::unpack(&collateral_vault_account.data.borrow())?;
+        if !collateral_vault_state.owner.eq(vault_authority_account.key)
+            || collateral_vault_state.amount == 0
+        {
+            msg!("Loan Fund: Collateral Vault is not held by the PDA");
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        // Checking if the loan is still waiting for a lender
+        if loan_state.is_funded() {
+            msg!("Loan Fund: Loan has already been funded");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if lender_account.key.eq(&loan_state.borrower_pubkey) {
+            msg!("Loan Fund: Borrower can't fund their own loan");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // BUSINESS LOGIC STARTS
+        let clock = Clock::get()?;
+        loan_state.lender_pubkey = *lender_account.key;
+        loan_state.repayment_deadline = clock
+            .unix_timestamp
+            .checked_add(loan_state.loan_duration)
+            .ok_or(ProgramError::InvalidArgument)?;
+        loan_state.serialize(&mut (&mut loan_account.data.borrow_mut()[..]))?;
+
+        msg!(
+            "Lending {} to the borrower, due by {}",
+            loan_state.principal_amount,
+            loan_state.repayment_deadline
+        );
+        Self::transfer_tokens(
+            token_program,
+            lender_y_token_account,
+            borrower_y_token_account,
+            lender_account,
+            &[],
+            loan_state.principal_amount,
+        )
+    }
+
+    // Repay loan processor
+    fn process_repay_loan(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let borrower_account = next_account_info(accounts_iterable)?;
+        let borrower_y_token_account = next_account_info(accounts_iterable)?;
+        let lender_y_token_account = next_account_info(accounts_iterable)?;
+        let borrower_x_token_account = next_account_info(accounts_iterable)?;
+        let loan_account = next_account_info(accounts_iterable)?;
+        let collateral_vault_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the borrower has signed the transaction
+        if !borrower_account.is_signer {
+            msg!("Loan Repay: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let loan_state = Self::unpack_loan_state(program_id, loan_account)?;
+        Self::validate_loan_vault(
+            program_id,
+            &loan_state,
+            loan_account,
+            collateral_vault_account,
+            vault_authority_account,
+        )?;
+
+        if !loan_state.borrower_pubkey.eq(borrower_account.key) {
+            msg!("Loan Repay: Caller is not the borrower");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the loan is running
+        let clock = Clock::get()?;
+        if !loan_state.is_funded() || clock.unix_timestamp >= loan_state.repayment_deadline {
+            msg!("Loan Repay: Loan is not funded or past its deadline");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // The repayment goes to the lender's associated token account, recreated if the lender closed it
+        Self::prepare_associated_token_account(
+            borrower_account,
+            &loan_state.lender_pubkey,
+            lender_y_token_account,
+            &loan_state.y_token_mint_pubkey,
+            token_program,
+            accounts_iterable,
+        )?;
+
+        // BUSINESS LOGIC STARTS
+        let repayment_amount = loan_state
+            .principal_amount
+            .checked_add(loan_state.interest_amount)
+            .ok_or(ProgramError::InvalidArgument)?;
+        msg!("Repaying {} to the lender", repayment_amount);
+        Self::transfer_tokens(
+            token_program,
+            borrower_y_token_account,
+            lender_y_token_account,
+            borrower_account,
+            &[],
+            repayment_amount,
+        )?;
+
+        msg!("Returning the collateral to the borrower");
+        Self::release_loan_collateral(
+            &loan_state,
+            loan_account,
+            collateral_vault_account,
+            borrower_x_token_account,
+            borrower_account,
+            token_program,
+            vault_authority_account,
+        )
+    }
+
+    // Claim collateral processor
+    fn process_claim_collateral(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let lender_account = next_account_info(accounts_iterable)?;
+        let lender_x_token_account = next_account_info(accounts_iterable)?;
+        let loan_account = next_account_info(accounts_iterable)?;
+        let collateral_vault_account = next_account_info(accounts_iterable)?;
+        let borrower_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the lender has signed the transaction
+        if !lender_account.is_signer {
+            msg!("Loan Claim Collateral: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let loan_state = Self::unpack_loan_state(program_id, loan_account)?;
+        Self::validate_loan_vault(
+            program_id,
+            &loan_state,
+            loan_account,
+            collateral_vault_account,
+            vault_authority_account,
+        )?;
+
+        if !loan_state.lender_pubkey.eq(lender_account.key)
+            || !loan_state.borrower_pubkey.eq(borrower_account.key)
+        {
+            msg!("Loan Claim Collateral: Passed accounts mismatch with the state's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the borrower has defaulted (only funded loans have a lender)
+        let clock = Clock::get()?;
+        if clock.unix_timestamp < loan_state.repayment_deadline {
+            msg!("Loan Claim Collateral: Loan is not past its deadline yet");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // BUSINESS LOGIC STARTS
+        msg!("Transferring the collateral to the lender");
+        Self::release_loan_collateral(
+            &loan_state,
+            loan_account,
+            collateral_vault_account,
+            lender_x_token_account,
+            borrower_account,
+            token_program,
+            vault_authority_account,
+        )
+    }
+
+    // Cancel loan request processor
+    fn process_cancel_loan_request(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iterable = &mut accounts.iter();
+        // All accounts
+        let borrower_account = next_account_info(accounts_iterable)?;
+        let borrower_x_token_account = next_account_info(accounts_iterable)?;
+        let loan_account = next_account_info(accounts_iterable)?;
+        let collateral_vault_account = next_account_info(accounts_iterable)?;
+        let token_program = next_account_info(accounts_iterable)?;
+        let vault_authority_account = next_account_info(accounts_iterable)?;
+
+        // Checking if the borrower has signed the transaction
+        if !borrower_account.is_signer {
+            msg!("Loan Cancel: Caller has not signed the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let loan_state = Self::unpack_loan_state(program_id, loan_account)?;
+        Self::validate_loan_vault(
+            program_id,
+            &loan_state,
+            loan_account,
+            collateral_vault_account,
+            vault_authority_account,
+        )?;
+
+        if !loan_state.borrower_pubkey.eq(borrower_account.key) {
+            msg!("Loan Cancel: Caller is not the borrower");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Checking if the request is still unfunded
+        if loan_state.is_funded() {
+            msg!("Loan Cancel: Loan has already been funded");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // BUSINESS LOGIC STARTS
+        msg!("Returning the collateral to the borrower");
+        Self::release_loan_collateral(
+            &loan_state,
+            loan_account,
+            collateral_vault_account,
+            borrower_x_token_account,
+            borrower_account,
+            token_program,
+            vault_authority_account,
+        )
+    }
+
+    // Reads a sealed bid account, making sure it is a sealed bid of the passed escrow owned by this program
+    fn unpack_sealed_bid_state(
+        program_id: &Pubkey,
+        sealed_bid_account: &AccountInfo,
+        escrow_pubkey: &Pubkey,
+    ) -> Result<SealedBidState, ProgramError> {
+        if !sealed_bid_account.owner.eq(program_id) {
+            msg!("Incorrect Sealed Bid Account passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let sealed_bid_state = SealedBidState::try_from_slice(&sealed_bid_account.data.borrow())
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        if sealed_bid_state.account_type != AccountType::SealedBid
+            || !sealed_bid_state.escrow_pubkey.eq(escrow_pubkey)
+        {
+            msg!("Passed Sealed Bid Account does not belong to the escrow");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(sealed_bid_state)
+    }
+
+    // Creates an account owned by this program at the PDA of the passed seeds (payer pays the rent)
+    fn create_program_account<'a>(
+        program_id: &Pubkey,
+        payer_account: &AccountInfo<'a>,
+        program_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        signer_seeds: &[&[u8]],
+        space: usize,
+    ) -> ProgramResult {
+        if !program_account.data_is_empty() {
+            msg!("Account to create is already in use");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let rent = Rent::get()?;
+        let create_account_ix = create_account(
+            payer_account.key,
+            program_account.key,
+            rent.minimum_balance(space),
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer_account.clone(),
+                program_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+
+    // Reallocates a program owned account to the given space, topping its rent up from the payer
+    fn grow_program_account<'a>(
+        payer_account: &AccountInfo<'a>,
+        program_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+    ) -> ProgramResult {
+        if !solana_program::system_program::check_id(system_program.key) {
+            msg!("Incorrect system program passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        program_account.realloc(space, false)?;
+
+        let rent = Rent::get()?;
+        let missing_lamports = rent
+            .minimum_balance(space)
+            .saturating_sub(program_account.lamports());
+        if missing_lamports == 0 {
+            return Ok(());
+        }
+
+        invoke(
+            &system_transfer(payer_account.key, program_account.key, missing_lamports),
+            &[
+                payer_account.clone(),
+                program_account.clone(),
+                system_program.clone(),
+            ],
+        )
+    }
+
+    // Moves a program owned account's lamports (escrow state, sealed bid...) to the rent refund recipient and wipes its data
+    fn close_program_account(
+        program_account: &AccountInfo,
+        rent_refund_account: &AccountInfo,
+    ) -> ProgramResult {
+        let program_account_balance = program_account.lamports();
+        **rent_refund_account.lamports.borrow_mut() = rent_refund_account
+            .lamports()
+            .checked_add(program_account_balance)
+            .ok_or(ProgramError::InsufficientFunds)?;
+
+        **program_account.lamports.borrow_mut() = 0;
+        *program_account.data.borrow_mut() = &mut [];
+
+        Ok(())
+    }
+
+    // Validates Bob's X token account, creating it as his associated token account when it does not exist yet
+    fn prepare_bob_x_token_account<'a>(
+        bob_account: &AccountInfo<'a>,
+        bob_x_token_account: &AccountInfo<'a>,
+        x_token_mint: &Pubkey,
+        token_program: &AccountInfo<'a>,
+        remaining_accounts: &mut Iter<AccountInfo<'a>>,
+    ) -> ProgramResult {
+        // Account already exists, so it has to be able to hold the X tokens on Bob's behalf
+        if !bob_x_token_account.data_is_empty() {
+            if !spl_token::check_id(bob_x_token_account.owner) {
+                msg!("Escrow Exchange: Bob's X token account is not owned by the token program");
+                return Err(ProgramError::IncorrectProgramId);
+            }
+
+            let bob_x_token_account_state = TokenState::unpack(&bob_x_token_account.data.borrow())?;
+
+            if !bob_x_token_account_state.mint.eq(x_token_mint) {
+                msg!("Escrow Exchange: Bob's X token account mint mismatch with PDA's temp X token account mint");
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // Even his associated token account could have had its ownership handed over since
+            if !bob_x_token_account_state.owner.eq(bob_account.key) {
+                msg!("Escrow Exchange: Bob's X token account is not owned by him");
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            return Ok(());
+        }
+
+        // Only the canonical associated token account can be created on the fly
+        Self::create_associated_token_account_for(
+            bob_account,
+            bob_account,
+            bob_x_token_account,
+            x_token_mint,
+            token_program,
+            remaining_accounts,
+        )
+    }
+
+    // Makes sure the token account is the wallet's associated token account, creating it when it does not exist yet
+    fn prepare_associated_token_account<'a>(
+        payer_account: &AccountInfo<'a>,
+        wallet_pubkey: &Pubkey,
+        associated_token_account: &AccountInfo<'a>,
+        token_mint: &Pubkey,
+        token_program: &AccountInfo<'a>,
+        remaining_accounts: &mut Iter<AccountInfo<'a>>,
+    ) -> ProgramResult {
+        if !get_associated_token_address(wallet_pubkey, token_mint).eq(associated_token_account.key)
+        {
+            msg!(
+                "Passed token account is not the associated token account of {}",
+                wallet_pubkey.to_string()
+            );
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        if !associated_token_account.data_is_empty() {
+            return Ok(());
+        }
+
+        // The wallet has to be passed to create its associated token account
+        let wallet_account = next_account_info(remaining_accounts)?;
+        if !wallet_account.key.eq(wallet_pubkey) {
+            msg!("Passed wallet mismatch with the associated token account's wallet");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Self::create_associated_token_account_for(
+            payer_account,
+            wallet_account,
+            associated_token_account,
+            token_mint,
+            token_program,
+            remaining_accounts,
+        )
+    }
+
+    // Creates the wallet's associated token account through the Associated Token Account program (payer pays the rent)
+    fn create_associated_token_account_for<'a>(
+        payer_account: &AccountInfo<'a>,
+        wallet_account: &AccountInfo<'a>,
+        associated_token_account: &AccountInfo<'a>,
+        token_mint: &Pubkey,
+        token_program: &AccountInfo<'a>,
+        remaining_accounts: &mut Iter<AccountInfo<'a>>,
+    ) -> ProgramResult {
+        if !get_associated_token_address(wallet_account.key, token_mint)
+            .eq(associated_token_account.key)
+        {
+            msg!("Passed token address is not the wallet's associated token address");
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let token_mint_account = next_account_info(remaining_accounts)?;
+        let system_program = next_account_info(remaining_accounts)?;
+        let associated_token_program = next_account_info(remaining_accounts)?;
+        let rent_sysvar = next_account_info(remaining_accounts)?;
+
+        if !token_mint_account.key.eq(token_mint) {
+            msg!("Passed token mint mismatch with the associated token account's mint");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !spl_associated_token_account::check_id(associated_token_program.key) {
+            msg!("Incorrect associated token account program passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        msg!(
+            "Creating associated token account of {} (rent paid by {})",
+            wallet_account.key.to_string(),
+            payer_account.key.to_string()
+        );
+        let create_associated_token_account_ix =
+            create_associated_token_account(payer_account.key, wallet_account.key, token_mint);
+
+        invoke(
+            &create_associated_token_account_ix,
+            &[
+                payer_account.clone(),
+                associated_token_account.clone(),
+                wallet_account.clone(),
+                token_mint_account.clone(),
+                system_program.clone(),
+                token_program.clone(),
+                rent_sysvar.clone(),
+                associated_token_program.clone(),
+            ],
+        )
+    }
+
+    // Makes sure the X token account lets the PDA move the offered amount on its owner's behalf
+    fn validate_x_delegation(
+        x_token_account: &AccountInfo,
+        owner_pubkey: &Pubkey,
+        delegate_pubkey: &Pubkey,
+        x_token_amount: u64,
+    ) -> ProgramResult {
+        if !spl_token::check_id(x_token_account.owner) {
+            msg!("Passed X Token account is not owned by the token program");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let x_token_account_state = TokenState::unpack(&x_token_account.data.borrow())?;
+        if !x_token_account_state.owner.eq(owner_pubkey) {
+            msg!("Passed X Token account is not Alice's");
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        if x_token_account_state.delegate != COption::Some(*delegate_pubkey)
+            || x_token_account_state.delegated_amount < x_token_amount
+        {
+            msg!("X Token delegation to the Escrow PDA is missing, revoked or too small");
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        if x_token_account_state.amount < x_token_amount {
+            msg!("X Token balance is below the offered amount");
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        Ok(())
+    }
+
+    // Validates the arbiter and counterparty passed while initializing an escrow
+    fn validate_arbitration(
+        arbitration: &Arbitration,
+        alice_pubkey: &Pubkey,
+        is_fiat_trade: bool,
+    ) -> ProgramResult {
+        if arbitration.fee_basis_points > TOTAL_BASIS_POINTS {
+            msg!("Escrow Initialize: Arbiter fee can't be more than the whole vault");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // The buyer of a fiat trade only becomes the counterparty when reserving it
+        if is_fiat_trade {
+            if arbitration.counterparty_pubkey != Pubkey::default() {
+                msg!("Escrow Initialize: Fiat trade counterparty is set by the reserving buyer");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            if arbitration.arbiter_pubkey == Pubkey::default()
+                || arbitration.arbiter_pubkey.eq(alice_pubkey)
+            {
+                msg!("Escrow Initialize: Alice and the arbiter have to be distinct");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            return Ok(());
+        }
+
+        // The arbiter has to be a third party
+        if arbitration.arbiter_pubkey == Pubkey::default()
+            || arbitration.counterparty_pubkey == Pubkey::default()
+            || arbitration.arbiter_pubkey.eq(alice_pubkey)
+            || arbitration
+                .arbiter_pubkey
+                .eq(&arbitration.counterparty_pubkey)
+            || arbitration.counterparty_pubkey.eq(alice_pubkey)
+        {
+            msg!("Escrow Initialize: Alice, the counterparty and the arbiter have to be distinct");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(())
+    }
+
+    // Validates the mode specific parameters passed while initializing an escrow
+    fn validate_escrow_mode(mode: &EscrowMode) -> ProgramResult {
+        match mode {
+            EscrowMode::FixedPrice => Ok(()),
+            EscrowMode::DutchAuction(dutch_auction) => {
+                if dutch_auction.start_price < dutch_auction.end_price {
+                    msg!("Escrow Initialize: Dutch auction start price below its end price");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                if dutch_auction.end_timestamp <= dutch_auction.start_timestamp {
+                    msg!("Escrow Initialize: Dutch auction has to end after it starts");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                if let PriceDecay::Exponential { half_life } = dutch_auction.decay {
+                    if half_life <= 0 {
+                        msg!("Escrow Initialize: Dutch auction half life should be positive");
+                        return Err(ProgramError::InvalidInstructionData);
+                    }
+                }
+
+                Ok(())
+            }
+            EscrowMode::EnglishAuction(english_auction) => {
+                let clock = Clock::get()?;
+                if english_auction.end_timestamp <= clock.unix_timestamp {
+                    msg!("Escrow Initialize: English auction has to end in the future");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                Ok(())
+            }
+            EscrowMode::HashTimeLock(hash_time_lock) => {
+                let clock = Clock::get()?;
+                if hash_time_lock.timelock <= clock.unix_timestamp {
+                    msg!("Escrow Initialize: Timelock has to be in the future");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                if hash_time_lock.recipient_pubkey == Pubkey::default() {
+                    msg!("Escrow Initialize: Hash time lock recipient is missing");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                Ok(())
+            }
+            EscrowMode::FiatTrade(fiat_trade) => {
+                if fiat_trade.payment_window <= 0 || fiat_trade.confirmation_window <= 0 {
+                    msg!("Escrow Initialize: Fiat trade windows have to be positive");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                Ok(())
+            }
+            EscrowMode::Bundle(bundle) => {
+                if bundle.x_legs.is_empty() && bundle.y_legs.is_empty() {
+                    msg!("Escrow Initialize: Bundle has no extra legs");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                if bundle.x_legs.len() > MAX_BUNDLE_LEGS || bundle.y_legs.len() > MAX_BUNDLE_LEGS {
+                    msg!(
+                        "Escrow Initialize: Bundles can have up to {} extra legs on each side",
+                        MAX_BUNDLE_LEGS
+                    );
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                if bundle
+                    .x_legs
+                    .iter()
+                    .chain(bundle.y_legs.iter())
+                    .any(|leg| leg.amount == 0)
+                {
+                    msg!("Escrow Initialize: Bundle leg amounts have to be positive");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                Ok(())
+            }
+            EscrowMode::SealedBidAuction(sealed_bid_auction) => {
+                let clock = Clock::get()?;
+                if sealed_bid_auction.commit_end_timestamp <= clock.unix_timestamp {
+                    msg!("Escrow Initialize: Sealed-bid commit window has to end in the future");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                if sealed_bid_auction.reveal_end_timestamp
+                    <= sealed_bid_auction.commit_end_timestamp
+                {
+                    msg!("Escrow Initialize: Sealed-bid reveal window has to end after the commit window");
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    // Validates the revenue split table passed while initializing an escrow
+    fn validate_revenue_splits(
+        revenue_splits: &[RevenueSplit],
+        dust_recipient_index: u8,
+    ) -> ProgramResult {
+        if revenue_splits.len() > MAX_REVENUE_SPLIT_RECIPIENTS {
+            msg!(
+                "Escrow Initialize: Proceeds can be split between {} recipients at most",
+                MAX_REVENUE_SPLIT_RECIPIENTS
+            );
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if usize::from(dust_recipient_index) >= revenue_splits.len() {
+            msg!("Escrow Initialize: Dust recipient index out of the revenue split table");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let mut total_basis_points: u16 = 0;
+        for revenue_split in revenue_splits {
+            if revenue_split.basis_points == 0 {
+                msg!("Escrow Initialize: Revenue split recipient without a share");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            total_basis_points = total_basis_points
+                .checked_add(revenue_split.basis_points)
+                .ok_or(ProgramError::InvalidInstructionData)?;
+        }
+
+        if total_basis_points != TOTAL_BASIS_POINTS {
+            msg!(
+                "Escrow Initialize: Revenue split basis points add up to {} instead of {}",
+                total_basis_points,
+                TOTAL_BASIS_POINTS
+            );
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(())
+    }
+
+    // Every recipient gets its share rounded down, the leftover dust goes to the designated recipient
+    fn split_y_proceeds(
+        revenue_splits: &[RevenueSplit],
+        dust_recipient_index: u8,
+        y_token_amount: u64,
+    ) -> Result<Vec<u64>, ProgramError> {
+        let mut split_amounts = Vec::with_capacity(revenue_splits.len());
+        for revenue_split in revenue_splits.iter() {
+            split_amounts.push(Self::basis_points_of(
+                y_token_amount,
+                revenue_split.basis_points,
+            )?);
+        }
+
+        let dust_amount = split_amounts
+            .iter()
+            .try_fold(y_token_amount, |remaining, amount| {
+                remaining.checked_sub(*amount)
+            })
+            .ok_or(ProgramError::InvalidArgument)?;
+        let dust_recipient_amount = split_amounts
+            .get_mut(usize::from(dust_recipient_index))
+            .ok_or(ProgramError::InvalidAccountData)?;
+        *dust_recipient_amount = dust_recipient_amount
+            .checked_add(dust_amount)
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        Ok(split_amounts)
+    }
+
+    // Empties every extra X vault of a bundle into the token account following it, then closes the vault
+    fn release_bundle_x_legs<'a>(
+        bundle: &Bundle,
+        escrow_pda_account: &AccountInfo<'a>,
+        pda_seed_bump_combination: &[&[u8]],
+        rent_refund_account: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        remaining_accounts: &mut Iter<AccountInfo<'a>>,
+    ) -> ProgramResult {
+        for x_leg in bundle.x_legs.iter() {
+            let x_vault_account = next_account_info(remaining_accounts)?;
+            let destination_token_account = next_account_info(remaining_accounts)?;
+            if !x_leg.token_pubkey.eq(x_vault_account.key) {
+                msg!("Passed X Vault mismatch with the bundle's X leg");
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // Whatever landed in the vault since goes along with the leg, so that it can be closed
+            let x_vault_state = TokenState::unpack(&x_vault_account.data.borrow())?;
+            msg!("Transferring {} tokens of an X leg", x_vault_state.amount);
+            Self::transfer_tokens(
+                token_program,
+                x_vault_account,
+                destination_token_account,
+                escrow_pda_account,
+                &[pda_seed_bump_combination],
+                x_vault_state.amount,
+            )?;
+
+            Self::close_vault(
+                token_program,
+                x_vault_account,
+                rent_refund_account,
+                escrow_pda_account,
+                &[pda_seed_bump_combination],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Pays the royalties of the NFT's metadata creators out of the Y price, returning what is left for Alice
+    fn pay_nft_royalties<'a>(
+        nft: &NftListing,
+        y_token_amount: u64,
+        y_token_mint: &Pubkey,
+        source_y_token_account: &AccountInfo<'a>,
+        source_authority: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        remaining_accounts: &mut Iter<AccountInfo<'a>>,
+    ) -> Result<u64, ProgramError> {
+        let nft_metadata_account = next_account_info(remaining_accounts)?;
+        if !nft.metadata_pubkey.eq(nft_metadata_account.key) {
+            msg!("Escrow Exchange: Passed NFT Metadata Account mismatch with the listing's");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // The metadata is read at every fill as the royalties may have been updated since the listing
+        let nft_metadata = metadata::metadata_of(nft_metadata_account, &nft.mint_pubkey)?;
+        if nft_metadata.seller_fee_basis_points > TOTAL_BASIS_POINTS {
+            msg!("Escrow Exchange: NFT royalties exceed the price");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let royalty_amount =
+            Self::basis_points_of(y_token_amount, nft_metadata.seller_fee_basis_points)?;
+        let mut remaining_amount = y_token_amount;
+        for creator in nft_metadata.creators.unwrap_or_default() {
+            let creator_y_token_account = next_account_info(remaining_accounts)?;
+            let creator_y_token_account_state =
+                TokenState::unpack(&creator_y_token_account.data.borrow())?;
+            if !creator_y_token_account_state.owner.eq(&creator.address)
+                || !creator_y_token_account_state.mint.eq(y_token_mint)
+            {
+                msg!("Escrow Exchange: Passed creator Y Token Account mismatch with the metadata's creator");
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // Creator shares are percentages of the royalties, the rounding dust stays with Alice
+            let creator_amount =
+                Self::basis_points_of(royalty_amount, u16::from(creator.share) * 100)?;
+            remaining_amount = remaining_amount
+                .checked_sub(creator_amount)
+                .ok_or(ProgramError::InvalidAccountData)?;
+
+            if creator_amount == 0 {
+                continue;
+            }
+
+            msg!(
+                "Transferring {} Y Tokens of royalties to creator {}",
+                creator_amount,
+                creator.address.to_string()
+            );
+            Self::transfer_tokens(
+                token_program,
+                source_y_token_account,
+                creator_y_token_account,
+                source_authority,
+                &[],
+                creator_amount,
+            )?;
+        }
+
+        Ok(remaining_amount)
+    }
+
+    // Checks that Alice's Y token account still belongs to the Y beneficiary recorded in the escrow and holds the paid mint
+    fn validate_y_beneficiary_account(
+        escrow_account_state: &EscrowState,
+        alice_y_token_account: &AccountInfo,
+        y_token_mint: &Pubkey,
+    ) -> ProgramResult {
+        if !spl_token::check_id(alice_y_token_account.owner) {
+            msg!("Alice's Y Token account is not owned by the token program");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let alice_y_token_account_state = TokenState::unpack(&alice_y_token_account.data.borrow())?;
+        if !escrow_account_state
+            .y_beneficiary_pubkey
+            .eq(&alice_y_token_account_state.owner)
+        {
+            msg!("Alice's Y Token account owner mismatch with Escrow state's Y beneficiary");
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        if !alice_y_token_account_state.mint.eq(y_token_mint) {
+            msg!("Alice's Y Token account mint mismatch with the paid Y mint");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    // Transfers the Y proceeds from Bob to Alice's Y token account, or splits them between the revenue split recipients
+    #[allow(clippy::too_many_arguments)]
+    fn transfer_y_proceeds<'a>(
+        escrow_account_state: &EscrowState,
+        y_token_amount: u64,
+        source_y_token_account: &AccountInfo<'a>,
+        source_authority: &AccountInfo<'a>,
+        signer_seeds: &[&[&[u8]]],
+        alice_y_token_account: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        remaining_accounts: &mut Iter<AccountInfo<'a>>,
+    ) -> ProgramResult {
+        if escrow_account_state.revenue_splits.is_empty() {
+            msg!("Transferring Y Tokens to Alice's Y Token Account");
+            return Self::transfer_tokens(
+                token_program,
+                source_y_token_account,
+                alice_y_token_account,
+                source_authority,
+                signer_seeds,
+                y_token_amount,
+            );
+        }
+
+        let split_amounts = Self::split_y_proceeds(
+            &escrow_account_state.revenue_splits,
+            escrow_account_state.dust_recipient_index,
+            y_token_amount,
+        )?;
+
+        for (revenue_split, split_amount) in escrow_account_state
+            .revenue_splits
+            .iter()
+            .zip(split_amounts)
+        {
+            let recipient_y_token_account = next_account_info(remaining_accounts)?;
+
+            if !revenue_split
+                .y_token_pubkey
+                .eq(recipient_y_token_account.key)
+            {
+                msg!(
+                    "Escrow Exchange: Passed revenue split recipient mismatch with Escrow state's"
+                );
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            if split_amount == 0 {
+                continue;
+            }
+
+            msg!(
+                "Transferring {} Y Tokens to revenue split recipient {}",
+                split_amount,
+                recipient_y_token_account.key.to_string()
+            );
+            Self::transfer_tokens(
+                token_program,
+                source_y_token_account,
+                recipient_y_token_account,
+                source_authority,
+                signer_seeds,
+                split_amount,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // Checks that the mint is an NFT: decimals 0, supply 1 and no mint authority other than its Metaplex master edition
+    fn validate_nft_mint(nft_mint_account: &AccountInfo) -> ProgramResult {
+        if !spl_token::check_id(nft_mint_account.owner) {
+            msg!("Passed NFT Mint is not a token mint");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let nft_mint_state = MintState::unpack(&nft_mint_account.data.borrow())?;
+        if nft_mint_state.decimals != 0 || nft_mint_state.supply != 1 {
+            msg!("Passed mint is not an NFT");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Master editions hold on to the mint authority of Metaplex NFTs without ever minting more
+        if let COption::Some(mint_authority) = nft_mint_state.mint_authority {
+            if !metadata::master_edition_address(nft_mint_account.key).eq(&mint_authority) {
+                msg!("Passed NFT Mint can still be minted by its mint authority");
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Checks that the token account holds the receipt of an escrow and belongs to the given holder
+    fn validate_receipt_holder(
+        receipt_token_account: &AccountInfo,
+        receipt_mint_pubkey: &Pubkey,
+        holder_pubkey: &Pubkey,
+    ) -> ProgramResult {
+        if !spl_token::check_id(receipt_token_account.owner) {
+            msg!("Passed receipt account is not a token account");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let receipt_token_account_state = TokenState::unpack(&receipt_token_account.data.borrow())?;
+        if !receipt_token_account_state.mint.eq(receipt_mint_pubkey)
+            || !receipt_token_account_state.owner.eq(holder_pubkey)
+            || receipt_token_account_state.amount != 1
+        {
+            msg!("Passed receipt account does not hold the escrow's receipt");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    // Checks that a mint passed at initialization is a fresh 0 decimals mint whose mint authority is the owner
+    fn validate_empty_mint(mint_account: &AccountInfo, authority_pubkey: &Pubkey) -> ProgramResult {
+        if !spl_token::check_id(mint_account.owner) {
+            msg!("Passed mint is not a token mint");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mint_state = MintState::unpack(&mint_account.data.borrow())?;
+        if mint_state.mint_authority != COption::Some(*authority_pubkey)
+            || mint_state.freeze_authority.is_some()
+            || mint_state.decimals != 0
+            || mint_state.supply != 0
+        {
+            msg!("Passed mint has to be new, with 0 decimals, no freeze authority and the caller as mint authority");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    // Checks that a vault passed at initialization is an empty token account of the right mint owned by Alice
+    fn validate_empty_vault(
+        vault_account: &AccountInfo,
+        owner_pubkey: &Pubkey,
+        token_mint: &Pubkey,
+    ) -> ProgramResult {
+        if !spl_token::check_id(vault_account.owner) {
+            msg!("Vault is not owned by the token program");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let vault_state = TokenState::unpack(&vault_account.data.borrow())?;
+
+        if !vault_state.owner.eq(owner_pubkey) {
+            msg!("Vault is not owned by the initializer");
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        if !vault_state.mint.eq(token_mint) {
+            msg!("Vault mint mismatch");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // A close authority other than the PDA would prevent the program from closing the vault
+        if vault_state.amount != 0 || vault_state.close_authority.is_some() {
+            msg!("Vault has to be empty and without a close authority");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    // Moves the ownership of a token account to a new owner (usually the escrow PDA)
+    fn transfer_token_account_ownership<'a>(
+        token_program: &AccountInfo<'a>,
+        token_account: &AccountInfo<'a>,
+        current_owner: &AccountInfo<'a>,
+        new_owner: &Pubkey,
+    ) -> ProgramResult {
+        // The handover is what the counterparties rely on, so it can't go through a lookalike program
+        if !spl_token::check_id(token_program.key) {
+            msg!("Incorrect token program passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let transfer_ownership_ix = set_authority(
+            token_program.key,
+            token_account.key,
+            Some(new_owner),
+            TokenAccountOwner,
+            current_owner.key,
+            &[current_owner.key],
+        )?;
+
+        invoke(
+            &transfer_ownership_ix,
+            &[
+                token_account.clone(),
+                current_owner.clone(),
+                token_program.clone(),
+            ],
+        )
+    }
+
+    // Moves a PDA owned vault under another PDA, signing with the current PDA's seeds
+    fn migrate_vault_authority<'a>(
+        token_program: &AccountInfo<'a>,
+        vault_account: &AccountInfo<'a>,
+        current_pda_account: &AccountInfo<'a>,
+        current_pda_seeds: &[&[u8]],
+        new_pda: &Pubkey,
+    ) -> ProgramResult {
+        // Signing with the PDA for a lookalike program would leave the vault behind while the state moves on
+        if !spl_token::check_id(token_program.key) {
+            msg!("Incorrect token program passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let migrate_authority_ix = set_authority(
+            token_program.key,
+            vault_account.key,
+            Some(new_pda),
+            TokenAccountOwner,
+            current_pda_account.key,
+            &[current_pda_account.key],
+        )?;
+
+        invoke_signed(
+            &migrate_authority_ix,
+            &[
+                vault_account.clone(),
+                current_pda_account.clone(),
+                token_program.clone(),
+            ],
+            &[current_pda_seeds],
+        )
+    }
+
+    // Closes an empty PDA owned vault, sending its rent to the destination
+    fn close_vault<'a>(
+        token_program: &AccountInfo<'a>,
+        vault_account: &AccountInfo<'a>,
+        destination_account: &AccountInfo<'a>,
+        escrow_pda_account: &AccountInfo<'a>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let close_vault_ix = close_token_account(
+            &token_program_id(),
+            vault_account.key,
+            destination_account.key,
+            escrow_pda_account.key,
+            &[escrow_pda_account.key],
+        )?;
+
+        invoke_signed(
+            &close_vault_ix,
+            &[
+                vault_account.clone(),
+                destination_account.clone(),
+                escrow_pda_account.clone(),
+                token_program.clone(),
+            ],
+            signer_seeds,
+        )
+    }
+
+    // Sweeps whatever is left in a PDA owned vault (tokens sent to it by anyone) to the token account, then closes it
+    fn sweep_and_close_vault<'a>(
+        token_program: &AccountInfo<'a>,
+        vault_account: &AccountInfo<'a>,
+        sweep_token_account: &AccountInfo<'a>,
+        destination_account: &AccountInfo<'a>,
+        escrow_pda_account: &AccountInfo<'a>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let vault_state = TokenState::unpack(&vault_account.data.borrow())?;
+        if vault_state.amount > 0 {
+            msg!(
+                "Sweeping {} leftover tokens out of the vault",
+                vault_state.amount
+            );
+            Self::transfer_tokens(
+                token_program,
+                vault_account,
+                sweep_token_account,
+                escrow_pda_account,
+                signer_seeds,
+                vault_state.amount,
+            )?;
+        }
+
+        Self::close_vault(
+            token_program,
+            vault_account,
+            destination_account,
+            escrow_pda_account,
+            signer_seeds,
+        )
+    }
+
+    // Transfers tokens between two token accounts, signing with the given seeds when the authority is a PDA
+    fn transfer_tokens<'a>(
+        token_program: &AccountInfo<'a>,
+        source_token_account: &AccountInfo<'a>,
+        destination_token_account: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> ProgramResult {
+        let transfer_tokens_ix = token_transfer(
+            &token_program_id(),
+            source_token_account.key,
+            destination_token_account.key,
+            authority.key,
+            &[authority.key],
+            amount,
+        )?;
+
+        invoke_signed(
+            &transfer_tokens_ix,
+            &[
+                source_token_account.clone(),
+                destination_token_account.clone(),
+                authority.clone(),
+                token_program.clone(),
+            ],
+            signer_seeds,
+        )
+    }
+
+    // Hands the mint authority of a mint over to the new authority (the PDA), or revokes it for good
+    fn transfer_mint_authority<'a>(
+        token_program: &AccountInfo<'a>,
+        mint_account: &AccountInfo<'a>,
+        current_authority: &AccountInfo<'a>,
+        new_authority: Option<&Pubkey>,
+    ) -> ProgramResult {
+        if !spl_token::check_id(token_program.key) {
+            msg!("Incorrect token program passed");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let transfer_authority_ix = set_authority(
+            token_program.key,
+            mint_account.key,
+            new_authority,
+            MintTokensAuthority,
+            current_authority.key,
+            &[current_authority.key],
+        )?;
+
+        invoke(
+            &transfer_authority_ix,
+            &[
+                mint_account.clone(),
+                current_authority.clone(),
+                token_program.clone(),
+            ],
+        )
+    }
+
+    // Mints tokens to a token account, signing with the given seeds when the mint authority is a PDA
+    fn mint_tokens<'a>(
+        token_program: &AccountInfo<'a>,
+        mint_account: &AccountInfo<'a>,
+        destination_token_account: &AccountInfo<'a>,
+        mint_authority: &AccountInfo<'a>,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> ProgramResult {
+        let mint_tokens_ix = mint_to(
+            &token_program_id(),
+            mint_account.key,
+            destination_token_account.key,
+            mint_authority.key,
+            &[mint_authority.key],
+            amount,
+        )?;
+
+        invoke_signed(
+            &mint_tokens_ix,
+            &[
+                mint_account.clone(),
+                destination_token_account.clone(),
+                mint_authority.clone(),
+                token_program.clone(),
+            ],
+            signer_seeds,
+        )
+    }
+
+    // Burns tokens of a token account, its owner being the signer
+    fn burn_tokens<'a>(
+        token_program: &AccountInfo<'a>,
+        token_account: &AccountInfo<'a>,
+        mint_account: &AccountInfo<'a>,
+        owner_account: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        let burn_tokens_ix = burn(
+            &token_program_id(),
+            token_account.key,
+            mint_account.key,
+            owner_account.key,
+            &[owner_account.key],
+            amount,
+        )?;
+
+        invoke(
+            &burn_tokens_ix,
+            &[
+                token_account.clone(),
+                mint_account.clone(),
+                owner_account.clone(),
+                token_program.clone(),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revenue_splits(basis_points: &[u16]) -> Vec<RevenueSplit> {
+        basis_points
+            .iter()
+            .map(|basis_points| RevenueSplit {
+                y_token_pubkey: Pubkey::new_unique(),
+                basis_points: *basis_points,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn revenue_splits_have_to_add_up_to_the_whole() {
+        assert!(
+            EscrowProcessor::validate_revenue_splits(&revenue_splits(&[5_000, 5_000]), 1).is_ok()
+        );
+        assert!(EscrowProcessor::validate_revenue_splits(&revenue_splits(&[10_000]), 0).is_ok());
+
+        assert_eq!(
+            EscrowProcessor::validate_revenue_splits(&revenue_splits(&[5_000, 4_999]), 0),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            EscrowProcessor::validate_revenue_splits(&revenue_splits(&[5_000, 5_001]), 0),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            EscrowProcessor::validate_revenue_splits(&revenue_splits(&[10_000, 0]), 0),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            EscrowProcessor::validate_revenue_splits(&revenue_splits(&[u16::MAX, 1]), 0),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn revenue_splits_need_a_dust_recipient_within_bounds() {
+        assert_eq!(
+            EscrowProcessor::validate_revenue_splits(&revenue_splits(&[5_000, 5_000]), 2),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            EscrowProcessor::validate_revenue_splits(&[], 0),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn revenue_splits_are_capped() {
+        let basis_points = [TOTAL_BASIS_POINTS / 11; 11];
+        assert_eq!(
+            EscrowProcessor::validate_revenue_splits(&revenue_splits(&basis_points), 0),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn y_proceeds_split_gives_the_dust_to_its_recipient() {
+        let splits = revenue_splits(&[3_333, 3_333, 3_334]);
+
+        assert_eq!(
+            EscrowProcessor::split_y_proceeds(&splits, 0, 100).unwrap(),
+            vec![34, 33, 33]
+        );
+        assert_eq!(
+            EscrowProcessor::split_y_proceeds(&splits, 2, 100).unwrap(),
+            vec![33, 33, 34]
+        );
+        assert_eq!(
+            EscrowProcessor::split_y_proceeds(&splits, 1, 1).unwrap(),
+            vec![0, 1, 0]
+        );
+    }
+
+    #[test]
+    fn y_proceeds_split_pays_out_everything() {
+        let splits = revenue_splits(&[1, 2_499, 7_500]);
+
+        for y_token_amount in [0, 1, 7, 9_999, 10_000, 123_456_789, u64::MAX] {
+            let split_amounts =
+                EscrowProcessor::split_y_proceeds(&splits, 1, y_token_amount).unwrap();
+            let total = split_amounts
+                .iter()
+                .fold(0u128, |total, amount| total + u128::from(*amount));
+            assert_eq!(total, u128::from(y_token_amount));
+        }
+    }
+
+    #[test]
+    fn escrow_account_grows_past_its_base_size_for_splits() {
+        // Clients create escrow accounts of the base size (`ESCROW_ACCOUNT_BASE_SPACE` in the scripts)
+        let escrow_account_data = [0u8; 180];
+        let mut escrow_state = EscrowState::unpack_from_slice(&escrow_account_data).unwrap();
+        assert!(!escrow_state.is_initialized());
+        assert_eq!(escrow_state.try_to_vec().unwrap().len(), 180);
+
+        escrow_state.revenue_splits = revenue_splits(&[5_000, 5_000]);
+        assert_eq!(escrow_state.try_to_vec().unwrap().len(), 180 + 2 * (32 + 2));
+    }
 }
@@ -1,7 +1,282 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::{program_pack::IsInitialized, pubkey::Pubkey};
+use solana_program::{
+    hash::hashv, program_error::ProgramError, program_pack::IsInitialized, pubkey::Pubkey,
+};
 // use solana_program::pubkey::Pubkey;
 
+/// Discriminator leading every account of this program other than the escrow state
+///
+/// The escrow state's leading `is_initialized` byte lines up with `Escrow`, so neither can be mistaken for the other
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum AccountType {
+    Uninitialized,
+    Escrow,
+    SealedBid,
+    MakerNonce,
+    MilestoneEscrow,
+    Vesting,
+    Stream,
+    Campaign,
+    Contribution,
+    Bounty,
+    CollectionBid,
+    MutualEscrow,
+    CallOption,
+    Loan,
+}
+
+/// Maximum number of recipients the Y proceeds of an escrow can be split between
+pub const MAX_REVENUE_SPLIT_RECIPIENTS: usize = 10;
+
+/// Maximum number of extra X legs, and of extra Y legs, a bundle escrow can have
+pub const MAX_BUNDLE_LEGS: usize = 4;
+
+/// Basis points making up the whole of the Y proceeds
+pub const TOTAL_BASIS_POINTS: u16 = 10_000;
+
+/// One recipient of the Y proceeds along with its share in basis points
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct RevenueSplit {
+    pub y_token_pubkey: Pubkey,
+    pub basis_points: u16,
+}
+
+/// How the price of a Dutch auction decays over time
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum PriceDecay {
+    /// Straight line from the start price to the end price
+    Linear,
+    /// Distance to the end price halves every `half_life` seconds (linear within each half life)
+    Exponential { half_life: i64 },
+}
+
+/// Dutch auction parameters, the Y price goes down from `start_price` to `end_price`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct DutchAuction {
+    pub start_price: u64,
+    pub end_price: u64,
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+    pub decay: PriceDecay,
+}
+
+impl DutchAuction {
+    /// Y price of the auction at the given unix timestamp
+    pub fn price_at(&self, unix_timestamp: i64) -> Result<u64, ProgramError> {
+        if unix_timestamp <= self.start_timestamp {
+            return Ok(self.start_price);
+        }
+
+        if unix_timestamp >= self.end_timestamp {
+            return Ok(self.end_price);
+        }
+
+        let price_range = u128::from(self.start_price - self.end_price);
+        let elapsed = (unix_timestamp - self.start_timestamp) as u128;
+
+        let remaining_price = match self.decay {
+            PriceDecay::Linear => {
+                let duration = (self.end_timestamp - self.start_timestamp) as u128;
+                price_range - price_range * elapsed / duration
+            }
+            PriceDecay::Exponential { half_life } => {
+                let half_life = half_life as u128;
+                let halvings = elapsed / half_life;
+                if halvings >= 128 {
+                    0
+                } else {
+                    let period_start_price = price_range >> halvings;
+                    let period_drop = period_start_price / 2;
+                    period_start_price - period_drop * (elapsed % half_life) / half_life
+                }
+            }
+        };
+
+        Ok(self.end_price + remaining_price as u64)
+    }
+}
+
+/// English auction parameters and current highest bid, X goes to the highest bidder once the auction ends
+///
+/// `bid_vault_pubkey`, `highest_bid` and `highest_bidder_pubkey` are set by the program
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct EnglishAuction {
+    pub end_timestamp: i64,
+    /// Lowest acceptable first bid
+    pub min_bid: u64,
+    /// Every new bid has to beat the highest bid by at least this much
+    pub min_bid_increment: u64,
+    /// PDA owned Y token account holding the highest bid
+    pub bid_vault_pubkey: Pubkey,
+    pub highest_bid: u64,
+    pub highest_bidder_pubkey: Pubkey,
+}
+
+impl EnglishAuction {
+    pub fn has_bids(&self) -> bool {
+        self.highest_bid > 0
+    }
+
+    /// Lowest bid that would be accepted right now
+    pub fn min_next_bid(&self) -> Result<u64, ProgramError> {
+        if !self.has_bids() {
+            return Ok(self.min_bid.max(1));
+        }
+
+        self.highest_bid
+            .checked_add(self.min_bid_increment.max(1))
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+}
+
+/// Sealed-bid auction parameters, bids are committed as hashes along with a Y deposit and revealed later on
+///
+/// `bid_vault_pubkey`, `open_bids`, `highest_bid`, `highest_bidder_pubkey` and `is_settled` are set by the program
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct SealedBidAuction {
+    /// Bids can be committed until then
+    pub commit_end_timestamp: i64,
+    /// Bids can be revealed between the end of the commit window and then
+    pub reveal_end_timestamp: i64,
+    /// Lowest valid bid
+    pub min_bid: u64,
+    /// Whether the deposits of bids which were never revealed go to Alice instead of back to the bidder
+    pub forfeit_unrevealed_deposits: bool,
+    /// PDA owned Y token account holding every deposit
+    pub bid_vault_pubkey: Pubkey,
+    /// Committed bids whose deposit has not been refunded yet
+    pub open_bids: u32,
+    /// Highest revealed bid
+    pub highest_bid: u64,
+    pub highest_bidder_pubkey: Pubkey,
+    /// Whether X has already been awarded
+    pub is_settled: bool,
+}
+
+impl SealedBidAuction {
+    pub fn has_bids(&self) -> bool {
+        self.highest_bid > 0
+    }
+}
+
+/// Hash time lock parameters for cross-chain atomic swaps, the Y leg happens on the other chain
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct HashTimeLock {
+    /// SHA-256 of the preimage releasing X
+    pub hashlock: [u8; 32],
+    /// Wallet whose associated X token account gets X once the preimage is revealed
+    pub recipient_pubkey: Pubkey,
+    /// X can be claimed until then, Alice can only take it back afterwards
+    pub timelock: i64,
+}
+
+impl HashTimeLock {
+    pub fn is_unlocked_by(&self, preimage: &[u8]) -> bool {
+        hashv(&[preimage]).to_bytes() == self.hashlock
+    }
+}
+
+/// Step a P2P fiat trade is at, the trade is released by closing the escrow
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum FiatTradeStatus {
+    /// Waiting for a buyer
+    Open,
+    /// A buyer claimed the trade and has to send the fiat payment
+    Reserved,
+    /// The buyer asserts the fiat payment was sent, Alice has to confirm it
+    PaidMarked,
+}
+
+/// One token of a bundle: the token account and the amount of it changing hands
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct BundleLeg {
+    /// PDA owned X vault for offered legs, Alice's token account receiving requested legs
+    pub token_pubkey: Pubkey,
+    pub amount: u64,
+}
+
+/// Extra legs of a bundle escrow, on top of the temp X token account and the expected Y amount
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct Bundle {
+    pub x_legs: Vec<BundleLeg>,
+    pub y_legs: Vec<BundleLeg>,
+}
+
+/// P2P fiat trade parameters, X is sold for a fiat payment happening off-chain
+///
+/// `status`, `buyer_pubkey` and `step_deadline` are set by the program
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct FiatTrade {
+    /// Seconds a buyer has to mark the payment as sent once reserved, the trade reopens afterwards
+    pub payment_window: i64,
+    /// Seconds Alice has to confirm the payment once marked, the trade escalates to the arbiter afterwards
+    pub confirmation_window: i64,
+    pub status: FiatTradeStatus,
+    pub buyer_pubkey: Pubkey,
+    /// Unix timestamp the current step times out at (0 while open)
+    pub step_deadline: i64,
+}
+
+/// Kind of escrow, along with the parameters specific to it
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Default)]
+pub enum EscrowMode {
+    /// Bob pays `expected_y_token_amount` for the whole life of the escrow
+    #[default]
+    FixedPrice,
+    /// Bob pays the current price of the auction
+    DutchAuction(DutchAuction),
+    /// Bidders lock Y until the auction ends, X goes to the highest one
+    EnglishAuction(EnglishAuction),
+    /// Bidders commit hashed bids and reveal them later, X goes to the highest revealed one
+    SealedBidAuction(SealedBidAuction),
+    /// X goes to the recipient against the preimage of the hashlock, back to Alice after the timelock
+    HashTimeLock(HashTimeLock),
+    /// X goes to a buyer once Alice confirms their off-chain fiat payment
+    FiatTrade(FiatTrade),
+    /// Bob pays `expected_y_token_amount` along with every extra Y leg, and gets every extra X leg along with the temp X tokens
+    Bundle(Bundle),
+}
+
+/// Where the offered X tokens sit until the escrow is filled
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Default)]
+pub enum XCustody {
+    /// Alice's temp X token account is handed over to the PDA
+    #[default]
+    Vault,
+    /// X stays in Alice's X token account, the PDA is only approved as its delegate
+    Delegate { x_token_amount: u64 },
+}
+
+/// NFT escrowed as X, the single unit of a decimals 0, supply 1 mint whose mint authority is gone or its Metaplex master edition
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct NftListing {
+    pub mint_pubkey: Pubkey,
+    /// Metaplex metadata PDA of the mint, validated at init so buyers can rely on what it advertises and read at every fill for the royalties
+    pub metadata_pubkey: Pubkey,
+}
+
+/// Single token whose holder is entitled to the proceeds and the cancellation of an escrow
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct EscrowReceipt {
+    pub mint_pubkey: Pubkey,
+    /// Mint Bob has to pay in, as the proceeds go to the holder's Y token account rather than the recorded one
+    pub y_token_mint_pubkey: Pubkey,
+}
+
+/// Arbiter settling disputes between Alice and a designated counterparty
+///
+/// `is_disputed` is set by the program
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct Arbitration {
+    pub arbiter_pubkey: Pubkey,
+    /// Only taker allowed to fill the escrow, and the other party of a dispute (the buyer reserving a fiat trade)
+    pub counterparty_pubkey: Pubkey,
+    /// Share of the vault the arbiter gets when resolving a dispute
+    pub fee_basis_points: u16,
+    /// Frozen escrows can only be resolved by the arbiter
+    pub is_disputed: bool,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct EscrowState {
     pub is_initialized: bool,
@@ -10,10 +285,672 @@ pub struct EscrowState {
     pub alice_y_token_pubkey: Pubkey,
     pub expected_y_token_amount: u64,
     pub escrow_pda_bump: u8,
+    /// Owner of the Y token account receiving the proceeds (Alice unless a beneficiary was given)
+    pub y_beneficiary_pubkey: Pubkey,
+    /// Account receiving the rent back once the escrow is closed (Alice unless set otherwise)
+    pub rent_refund_pubkey: Pubkey,
+    /// Recipients splitting the Y proceeds (empty when everything goes to Alice's Y token account)
+    pub revenue_splits: Vec<RevenueSplit>,
+    /// Index of the revenue split recipient getting the rounding dust
+    pub dust_recipient_index: u8,
+    pub mode: EscrowMode,
+    pub custody: XCustody,
+    /// Arbiter and counterparty (none when the escrow can't be disputed)
+    pub arbitration: Option<Arbitration>,
+    /// NFT being sold (none when X is a fungible token)
+    pub nft: Option<NftListing>,
+    /// Receipt token standing for Alice's position (none when the position stays with Alice's key)
+    pub receipt: Option<EscrowReceipt>,
+}
+
+impl EscrowState {
+    // The state is variable sized, so the account can be bigger than the serialized state
+    pub fn unpack_from_slice(data: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self::deserialize(&mut &data[..])?)
+    }
+
+    pub fn is_disputed(&self) -> bool {
+        self.arbitration
+            .as_ref()
+            .is_some_and(|arbitration| arbitration.is_disputed)
+    }
+
+    /// Y price Bob has to pay at the given unix timestamp (the lowest acceptable bid for auctions)
+    pub fn current_y_price(&self, unix_timestamp: i64) -> Result<u64, ProgramError> {
+        match &self.mode {
+            EscrowMode::FixedPrice => Ok(self.expected_y_token_amount),
+            EscrowMode::DutchAuction(dutch_auction) => dutch_auction.price_at(unix_timestamp),
+            EscrowMode::EnglishAuction(english_auction) => english_auction.min_next_bid(),
+            EscrowMode::SealedBidAuction(sealed_bid_auction) => Ok(sealed_bid_auction.min_bid),
+            EscrowMode::HashTimeLock(_) => Ok(0),
+            EscrowMode::FiatTrade(_) => Ok(self.expected_y_token_amount),
+            EscrowMode::Bundle(_) => Ok(self.expected_y_token_amount),
+        }
+    }
 }
 
 impl IsInitialized for EscrowState {
     fn is_initialized(&self) -> bool {
-        return self.is_initialized;
+        self.is_initialized
+    }
+}
+
+/// A bid committed to a sealed-bid auction, stored in a PDA derived from the escrow and the bidder
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SealedBidState {
+    pub account_type: AccountType,
+    pub escrow_pubkey: Pubkey,
+    pub bidder_pubkey: Pubkey,
+    /// SHA-256 of the escrow pubkey, the bidder pubkey, the bid amount (little endian) and a 32 byte salt
+    pub commitment: [u8; 32],
+    /// Y locked in the bid vault, caps the amount which can be revealed
+    pub deposit: u64,
+    pub is_revealed: bool,
+    pub revealed_amount: u64,
+    pub bump: u8,
+}
+
+impl SealedBidState {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 1 + 8 + 1;
+
+    /// Commitment a bidder has to submit for the given bid
+    pub fn commitment_for(
+        escrow_pubkey: &Pubkey,
+        bidder_pubkey: &Pubkey,
+        bid_amount: u64,
+        salt: &[u8; 32],
+    ) -> [u8; 32] {
+        hashv(&[
+            &escrow_pubkey.to_bytes(),
+            &bidder_pubkey.to_bytes(),
+            &bid_amount.to_le_bytes(),
+            salt,
+        ])
+        .to_bytes()
+    }
+}
+
+/// Order a maker signs off-chain, filled by a taker through `ExchangeSignedOrder`
+///
+/// The borsh serialization of this struct is the message signed with the maker's ed25519 key
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SignedOrder {
+    /// Escrow program the order is meant for, so that it can't be replayed elsewhere
+    pub program_id: Pubkey,
+    pub maker_pubkey: Pubkey,
+    /// Maker's X token account, delegated to the maker's order PDA (`["order", maker]`) for at least `x_token_amount`
+    pub maker_x_token_pubkey: Pubkey,
+    /// Y token account receiving the taker's payment
+    pub maker_y_token_pubkey: Pubkey,
+    pub x_token_amount: u64,
+    pub y_token_amount: u64,
+    /// Has to match the maker's nonce account when filled
+    pub nonce: u64,
+    /// Unix timestamp after which the order can no longer be filled
+    pub expiry_timestamp: i64,
+}
+
+/// Next nonce a maker's signed orders have to carry, stored in a PDA derived from the maker
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MakerNonceState {
+    pub account_type: AccountType,
+    pub maker_pubkey: Pubkey,
+    pub nonce: u64,
+    pub bump: u8,
+}
+
+impl MakerNonceState {
+    pub const LEN: usize = 1 + 32 + 8 + 1;
+}
+
+/// Maximum number of tranches a milestone escrow can be released in
+pub const MAX_MILESTONES: usize = 32;
+
+/// One tranche of a milestone escrow
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct Milestone {
+    pub amount: u64,
+    /// Unix timestamp the work is due by (0 when there is none), only recorded for both parties to see
+    pub deadline: i64,
+    pub is_released: bool,
+}
+
+/// Contract work escrow where the payer's deposit is released to the payee in tranches
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MilestoneEscrowState {
+    pub account_type: AccountType,
+    pub payer_pubkey: Pubkey,
+    /// Token account every tranche is released to
+    pub payee_token_pubkey: Pubkey,
+    /// PDA owned token account holding the unreleased tranches
+    pub vault_pubkey: Pubkey,
+    /// Bump of the vault authority, PDA of `["milestone", milestone escrow state]`
+    pub vault_authority_bump: u8,
+    /// Unreleased tranches can be refunded to the payer from then on
+    pub final_deadline: i64,
+    pub milestones: Vec<Milestone>,
+}
+
+impl MilestoneEscrowState {
+    // The state is variable sized, so the account can be bigger than the serialized state
+    pub fn unpack_from_slice(data: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self::deserialize(&mut &data[..])?)
+    }
+
+    pub fn unreleased_amount(&self) -> Result<u64, ProgramError> {
+        self.milestones
+            .iter()
+            .filter(|milestone| !milestone.is_released)
+            .try_fold(0u64, |total, milestone| total.checked_add(milestone.amount))
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+}
+
+/// Unlock schedule of a vesting escrow, nothing unlocks before the cliff and everything is unlocked at the end
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct VestingSchedule {
+    pub total_amount: u64,
+    pub start_timestamp: i64,
+    pub cliff_timestamp: i64,
+    pub end_timestamp: i64,
+    /// Seconds between two unlocks, the linear unlock is rounded down to whole periods
+    pub period: i64,
+    /// Whether the depositor can take the unvested tokens back
+    pub is_revocable: bool,
+}
+
+impl VestingSchedule {
+    /// Amount unlocked at the given unix timestamp
+    pub fn vested_amount(&self, unix_timestamp: i64) -> Result<u64, ProgramError> {
+        if unix_timestamp < self.cliff_timestamp {
+            return Ok(0);
+        }
+
+        if unix_timestamp >= self.end_timestamp {
+            return Ok(self.total_amount);
+        }
+
+        let elapsed = (unix_timestamp - self.start_timestamp) as u128;
+        let period = self.period as u128;
+        let duration = (self.end_timestamp - self.start_timestamp) as u128;
+
+        let vested_amount = u128::from(self.total_amount)
+            .checked_mul(elapsed / period * period)
+            .map(|amount| amount / duration)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        Ok(vested_amount as u64)
+    }
+}
+
+/// Tokens locked by a depositor and unlocked to a beneficiary over time
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct VestingState {
+    pub account_type: AccountType,
+    pub depositor_pubkey: Pubkey,
+    pub beneficiary_pubkey: Pubkey,
+    /// PDA owned token account holding the tokens which have not been withdrawn
+    pub vault_pubkey: Pubkey,
+    /// Bump of the vault authority, PDA of `["vesting", vesting state]`
+    pub vault_authority_bump: u8,
+    pub schedule: VestingSchedule,
+    pub withdrawn_amount: u64,
+    /// Unix timestamp the schedule was revoked at, nothing vests afterwards (0 when not revoked)
+    pub revoked_timestamp: i64,
+}
+
+impl VestingState {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 1 + (8 + 8 + 8 + 8 + 8 + 1) + 8 + 8;
+
+    /// Amount the beneficiary is entitled to at the given unix timestamp, taking a revocation into account
+    pub fn vested_amount(&self, unix_timestamp: i64) -> Result<u64, ProgramError> {
+        if self.revoked_timestamp != 0 {
+            return self
+                .schedule
+                .vested_amount(unix_timestamp.min(self.revoked_timestamp));
+        }
+
+        self.schedule.vested_amount(unix_timestamp)
+    }
+}
+
+/// Terms of a payment stream, the deposit flows to the recipient at a constant rate until it runs out
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct StreamTerms {
+    pub deposit_amount: u64,
+    /// Tokens accruing to the recipient every second
+    pub rate_per_second: u64,
+    /// Unix timestamp the stream starts flowing at
+    pub start_timestamp: i64,
+}
+
+impl StreamTerms {
+    /// Amount streamed to the recipient by the given unix timestamp
+    pub fn accrued_amount(&self, unix_timestamp: i64) -> u64 {
+        if unix_timestamp <= self.start_timestamp {
+            return 0;
+        }
+
+        let elapsed = (unix_timestamp - self.start_timestamp) as u128;
+        let streamed_amount = elapsed.saturating_mul(u128::from(self.rate_per_second));
+
+        streamed_amount.min(u128::from(self.deposit_amount)) as u64
+    }
+}
+
+/// Tokens streamed from a sender to a recipient by the second
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct StreamState {
+    pub account_type: AccountType,
+    pub sender_pubkey: Pubkey,
+    pub recipient_pubkey: Pubkey,
+    /// Token account the streamed tokens are paid to
+    pub recipient_token_pubkey: Pubkey,
+    /// PDA owned token account holding the tokens which have not been withdrawn
+    pub vault_pubkey: Pubkey,
+    /// Bump of the vault authority, PDA of `["stream", stream state]`
+    pub vault_authority_bump: u8,
+    pub terms: StreamTerms,
+    pub withdrawn_amount: u64,
+}
+
+impl StreamState {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 32 + 1 + (8 + 8 + 8) + 8;
+}
+
+/// Crowdfunding campaign pooling the Y deposits of many backers towards a goal
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct CampaignState {
+    pub account_type: AccountType,
+    pub creator_pubkey: Pubkey,
+    /// Y token account the pooled deposits are claimed to
+    pub creator_y_token_pubkey: Pubkey,
+    /// PDA owned Y token account pooling the deposits
+    pub vault_pubkey: Pubkey,
+    /// Bump of the vault authority, PDA of `["campaign", campaign state]`
+    pub vault_authority_bump: u8,
+    pub goal_amount: u64,
+    /// Backers can deposit until then, the campaign is settled afterwards
+    pub deadline: i64,
+    pub raised_amount: u64,
+    /// Contribution receipts which have not been closed yet
+    pub open_contributions: u32,
+    pub is_claimed: bool,
+}
+
+impl CampaignState {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 1 + 8 + 8 + 8 + 4 + 1;
+
+    pub fn is_goal_met(&self) -> bool {
+        self.raised_amount >= self.goal_amount
+    }
+}
+
+/// Receipt of a backer's deposits into a campaign, stored in a PDA derived from the campaign and the backer
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ContributionState {
+    pub account_type: AccountType,
+    pub campaign_pubkey: Pubkey,
+    pub backer_pubkey: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl ContributionState {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 1;
+}
+
+/// Maximum number of approvers who can award a bounty
+pub const MAX_BOUNTY_APPROVERS: usize = 10;
+
+/// Maximum number of hunters a bounty can be split between
+pub const MAX_BOUNTY_HUNTERS: usize = 10;
+
+/// Reward escrowed by a poster until one of the approvers awards it
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct BountyState {
+    pub account_type: AccountType,
+    pub poster_pubkey: Pubkey,
+    /// PDA owned token account holding the reward
+    pub vault_pubkey: Pubkey,
+    /// Bump of the vault authority, PDA of `["bounty", bounty state]`
+    pub vault_authority_bump: u8,
+    /// The poster can take the reward back from then on if nothing was awarded
+    pub expiry_timestamp: i64,
+    /// Any of them can award the reward
+    pub approvers: Vec<Pubkey>,
+}
+
+impl BountyState {
+    // The state is variable sized, so the account can be bigger than the serialized state
+    pub fn unpack_from_slice(data: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self::deserialize(&mut &data[..])?)
+    }
+}
+
+/// What makes an NFT eligible to fill a collection bid
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum CollectionCriteria {
+    /// Root of a sha256 Merkle tree whose leaves are the hashes of the allowed mints (pairs are hashed sorted)
+    MerkleRoot([u8; 32]),
+    /// Verified collection in the NFT's Metaplex metadata
+    VerifiedCollection(Pubkey),
+}
+
+impl CollectionCriteria {
+    /// Root of the Merkle tree the proof leads to from the mint's leaf
+    pub fn merkle_root_of(mint_pubkey: &Pubkey, proof: &[[u8; 32]]) -> [u8; 32] {
+        proof.iter().fold(
+            hashv(&[&mint_pubkey.to_bytes()]).to_bytes(),
+            |node, sibling| {
+                if node <= *sibling {
+                    hashv(&[&node, sibling]).to_bytes()
+                } else {
+                    hashv(&[sibling, &node]).to_bytes()
+                }
+            },
+        )
+    }
+}
+
+/// Bid of Y tokens for any NFT of a collection
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct CollectionBidState {
+    pub account_type: AccountType,
+    pub buyer_pubkey: Pubkey,
+    /// PDA owned Y token account holding the bid
+    pub vault_pubkey: Pubkey,
+    /// Bump of the vault authority, PDA of `["collection_bid", bid state]`
+    pub vault_authority_bump: u8,
+    pub bid_amount: u64,
+    pub criteria: CollectionCriteria,
+}
+
+impl CollectionBidState {
+    pub const LEN: usize = 1 + 32 + 32 + 1 + 8 + 33;
+}
+
+/// OTC trade where the maker and the taker both deposit their leg before either of them settles
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MutualEscrowState {
+    pub account_type: AccountType,
+    pub maker_pubkey: Pubkey,
+    pub taker_pubkey: Pubkey,
+    /// PDA owned token accounts holding the legs
+    pub x_vault_pubkey: Pubkey,
+    pub y_vault_pubkey: Pubkey,
+    /// Bump of the vault authority, PDA of `["mutual", mutual escrow state]`
+    pub vault_authority_bump: u8,
+    pub x_token_amount: u64,
+    pub y_token_amount: u64,
+    pub is_x_deposited: bool,
+    pub is_y_deposited: bool,
+}
+
+impl MutualEscrowState {
+    pub const LEN: usize = 1 + 32 * 4 + 1 + 8 + 8 + 1 + 1;
+
+    /// Whether both legs are in their vaults, after which only settling is possible
+    pub fn is_funded(&self) -> bool {
+        self.is_x_deposited && self.is_y_deposited
+    }
+}
+
+/// Covered call written on X, whoever holds the holder token can buy X at the strike until expiry
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct CallOptionState {
+    pub account_type: AccountType,
+    pub writer_pubkey: Pubkey,
+    /// Mint of the premium and the strike, paid into the writer's associated token account
+    pub y_token_mint_pubkey: Pubkey,
+    /// PDA owned token account holding the covered X
+    pub x_vault_pubkey: Pubkey,
+    /// Mint of the token representing the option position, the PDA being its mint authority
+    pub holder_mint_pubkey: Pubkey,
+    /// Bump of the PDA of `["option", call option state]`
+    pub vault_authority_bump: u8,
+    pub premium_amount: u64,
+    pub strike_amount: u64,
+    pub expiry_timestamp: i64,
+    /// Whether the premium has been paid and the holder token minted
+    pub is_sold: bool,
+}
+
+impl CallOptionState {
+    pub const LEN: usize = 1 + 32 * 4 + 1 + 8 + 8 + 8 + 1;
+}
+
+/// Y loan backed by X collateral, which goes to the lender if the loan isn't repaid in time
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct LoanState {
+    pub account_type: AccountType,
+    pub borrower_pubkey: Pubkey,
+    /// Token account the borrower gets the principal in
+    pub borrower_y_token_pubkey: Pubkey,
+    /// Set by the program once the loan is funded
+    pub lender_pubkey: Pubkey,
+    /// Mint of the principal, repaid into the lender's associated token account
+    pub y_token_mint_pubkey: Pubkey,
+    /// PDA owned token account holding the collateral
+    pub collateral_vault_pubkey: Pubkey,
+    /// Bump of the vault authority, PDA of `["loan", loan state]`
+    pub vault_authority_bump: u8,
+    pub principal_amount: u64,
+    pub interest_amount: u64,
+    /// Seconds the borrower has to repay once funded
+    pub loan_duration: i64,
+    /// Unix timestamp the repayment is due by (0 until funded)
+    pub repayment_deadline: i64,
+}
+
+impl LoanState {
+    pub const LEN: usize = 1 + 32 * 5 + 1 + 8 + 8 + 8 + 8;
+
+    pub fn is_funded(&self) -> bool {
+        self.lender_pubkey != Pubkey::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dutch_auction(decay: PriceDecay) -> DutchAuction {
+        DutchAuction {
+            start_price: 1_000,
+            end_price: 200,
+            start_timestamp: 100,
+            end_timestamp: 200,
+            decay,
+        }
+    }
+
+    #[test]
+    fn dutch_auction_price_is_clamped_to_its_window() {
+        for decay in [
+            PriceDecay::Linear,
+            PriceDecay::Exponential { half_life: 10 },
+        ] {
+            let dutch_auction = dutch_auction(decay);
+            assert_eq!(dutch_auction.price_at(i64::MIN).unwrap(), 1_000);
+            assert_eq!(dutch_auction.price_at(100).unwrap(), 1_000);
+            assert_eq!(dutch_auction.price_at(200).unwrap(), 200);
+            assert_eq!(dutch_auction.price_at(i64::MAX).unwrap(), 200);
+        }
+    }
+
+    #[test]
+    fn dutch_auction_price_decays_linearly() {
+        let dutch_auction = dutch_auction(PriceDecay::Linear);
+        assert_eq!(dutch_auction.price_at(125).unwrap(), 800);
+        assert_eq!(dutch_auction.price_at(150).unwrap(), 600);
+        assert_eq!(dutch_auction.price_at(199).unwrap(), 208);
+    }
+
+    #[test]
+    fn dutch_auction_price_halves_every_half_life() {
+        let dutch_auction = dutch_auction(PriceDecay::Exponential { half_life: 10 });
+        assert_eq!(dutch_auction.price_at(105).unwrap(), 800);
+        assert_eq!(dutch_auction.price_at(110).unwrap(), 600);
+        assert_eq!(dutch_auction.price_at(115).unwrap(), 500);
+        assert_eq!(dutch_auction.price_at(120).unwrap(), 400);
+        assert_eq!(dutch_auction.price_at(199).unwrap(), 201);
+    }
+
+    #[test]
+    fn dutch_auction_price_never_goes_up() {
+        for decay in [PriceDecay::Linear, PriceDecay::Exponential { half_life: 7 }] {
+            let dutch_auction = dutch_auction(decay);
+            let mut previous_price = dutch_auction.start_price;
+            for unix_timestamp in 90..210 {
+                let price = dutch_auction.price_at(unix_timestamp).unwrap();
+                assert!(price <= previous_price);
+                assert!(price >= dutch_auction.end_price);
+                previous_price = price;
+            }
+        }
+    }
+
+    fn vesting_schedule(period: i64) -> VestingSchedule {
+        VestingSchedule {
+            total_amount: 1_000,
+            start_timestamp: 1_000,
+            cliff_timestamp: 1_250,
+            end_timestamp: 2_000,
+            period,
+            is_revocable: true,
+        }
+    }
+
+    #[test]
+    fn nothing_vests_before_the_cliff() {
+        let schedule = vesting_schedule(1);
+        assert_eq!(schedule.vested_amount(i64::MIN).unwrap(), 0);
+        assert_eq!(schedule.vested_amount(1_000).unwrap(), 0);
+        assert_eq!(schedule.vested_amount(1_249).unwrap(), 0);
+    }
+
+    #[test]
+    fn cliff_unlocks_what_vested_since_the_start() {
+        let schedule = vesting_schedule(1);
+        assert_eq!(schedule.vested_amount(1_250).unwrap(), 250);
+        assert_eq!(schedule.vested_amount(1_500).unwrap(), 500);
+        assert_eq!(schedule.vested_amount(1_999).unwrap(), 999);
+    }
+
+    #[test]
+    fn vesting_is_rounded_down_to_whole_periods() {
+        let schedule = vesting_schedule(100);
+        assert_eq!(schedule.vested_amount(1_250).unwrap(), 200);
+        assert_eq!(schedule.vested_amount(1_299).unwrap(), 200);
+        assert_eq!(schedule.vested_amount(1_300).unwrap(), 300);
+        assert_eq!(schedule.vested_amount(1_999).unwrap(), 900);
+    }
+
+    #[test]
+    fn everything_vests_at_the_end() {
+        for period in [1, 100, 333] {
+            let schedule = vesting_schedule(period);
+            assert_eq!(schedule.vested_amount(2_000).unwrap(), 1_000);
+            assert_eq!(schedule.vested_amount(i64::MAX).unwrap(), 1_000);
+        }
+    }
+
+    #[test]
+    fn vesting_handles_the_largest_amounts() {
+        let schedule = VestingSchedule {
+            total_amount: u64::MAX,
+            ..vesting_schedule(1)
+        };
+        assert_eq!(schedule.vested_amount(1_500).unwrap(), u64::MAX / 2);
+        assert_eq!(schedule.vested_amount(2_000).unwrap(), u64::MAX);
+    }
+
+    fn stream_terms(rate_per_second: u64) -> StreamTerms {
+        StreamTerms {
+            deposit_amount: 1_000,
+            rate_per_second,
+            start_timestamp: 500,
+        }
+    }
+
+    #[test]
+    fn nothing_streams_before_the_start() {
+        let terms = stream_terms(3);
+        assert_eq!(terms.accrued_amount(i64::MIN), 0);
+        assert_eq!(terms.accrued_amount(0), 0);
+        assert_eq!(terms.accrued_amount(500), 0);
+    }
+
+    #[test]
+    fn stream_accrues_every_second() {
+        let terms = stream_terms(3);
+        assert_eq!(terms.accrued_amount(501), 3);
+        assert_eq!(terms.accrued_amount(600), 300);
+        assert_eq!(terms.accrued_amount(833), 999);
+    }
+
+    #[test]
+    fn stream_stops_once_the_deposit_runs_out() {
+        let terms = stream_terms(3);
+        assert_eq!(terms.accrued_amount(834), 1_000);
+        assert_eq!(terms.accrued_amount(i64::MAX), 1_000);
+
+        let terms = stream_terms(u64::MAX);
+        assert_eq!(terms.accrued_amount(501), 1_000);
+        assert_eq!(terms.accrued_amount(i64::MAX), 1_000);
+    }
+
+    fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        if left <= right {
+            hashv(&[left, right]).to_bytes()
+        } else {
+            hashv(&[right, left]).to_bytes()
+        }
+    }
+
+    #[test]
+    fn merkle_proof_leads_every_member_to_the_root() {
+        let mints: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = mints
+            .iter()
+            .map(|mint| hashv(&[&mint.to_bytes()]).to_bytes())
+            .collect();
+        let left_node = hash_pair(&leaves[0], &leaves[1]);
+        let right_node = hash_pair(&leaves[2], &leaves[3]);
+        let merkle_root = hash_pair(&left_node, &right_node);
+
+        let proofs = [
+            [leaves[1], right_node],
+            [leaves[0], right_node],
+            [leaves[3], left_node],
+            [leaves[2], left_node],
+        ];
+        for (mint, proof) in mints.iter().zip(proofs.iter()) {
+            assert_eq!(CollectionCriteria::merkle_root_of(mint, proof), merkle_root);
+        }
+
+        // Outsiders and tampered proofs lead somewhere else
+        assert_ne!(
+            CollectionCriteria::merkle_root_of(&Pubkey::new_unique(), &proofs[0]),
+            merkle_root
+        );
+        assert_ne!(
+            CollectionCriteria::merkle_root_of(&mints[0], &[leaves[2], right_node]),
+            merkle_root
+        );
+        assert_ne!(
+            CollectionCriteria::merkle_root_of(&mints[0], &proofs[0][..1]),
+            merkle_root
+        );
+    }
+
+    #[test]
+    fn merkle_root_of_a_single_mint_is_its_leaf() {
+        let mint = Pubkey::new_unique();
+        assert_eq!(
+            CollectionCriteria::merkle_root_of(&mint, &[]),
+            hashv(&[&mint.to_bytes()]).to_bytes()
+        );
     }
 }
@@ -62,7 +62,7 @@ import {
         keys: [
           {
             isSigner: true,
-            isWritable: false,
+            isWritable: true,
             pubkey: alice.publicKey,
           },
           {
@@ -37,6 +37,10 @@ export const KEYPAIRS = {
   masterAccount: generateKeypair(),
 };
 
+// Borsh size of an escrow state without options, the program grows the account when the options need more
+export const ESCROW_ACCOUNT_BASE_SPACE = 180;
+
+// Only the fixed width head of the escrow state, everything after the rent refund recipient is variable sized
 export const LAYOUTS = {
   tokenAccountLayout: AccountLayout,
   mintAccountLayout: MintLayout,
@@ -48,6 +52,8 @@ export const LAYOUTS = {
       blob(32, "aliceYTokenPubKey"),
       blob(8, "expectedYTokenAmount"),
       blob(1, "escrowPDABump"),
+      blob(32, "yBeneficiaryPubKey"),
+      blob(32, "rentRefundPubKey"),
     ],
     "EscrowState"
   ),
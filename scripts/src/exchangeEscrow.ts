@@ -67,7 +67,7 @@ import { getKeypair, getPublicKey, sleep } from "./utils";
         keys: [
           {
             isSigner: true,
-            isWritable: false,
+            isWritable: true,
             pubkey: bob.publicKey,
           },
           {
@@ -101,6 +101,8 @@ import {
               aliceYTokenPubKey,
               expectedYTokenAmount,
               escrowPDABump,
+              yBeneficiaryPubKey,
+              rentRefundPubKey,
             } = LAYOUTS["escrowAccountLayout"].decode(data) as EscrowLayout;
 
             console.log("ESCROW IS_INITIALIZED", isInitialized === 1);
@@ -118,6 +120,14 @@ import {
             );
             console.log("ESCROW EXPECTED_Y_TOKEN_AMOUNT", expectedYTokenAmount);
             console.log("ESCROW PDA BUMP", escrowPDABump);
+            console.log(
+              "ESCROW Y_BENEFICIARY_PUBKEY",
+              new PublicKey(yBeneficiaryPubKey).toString()
+            );
+            console.log(
+              "ESCROW RENT_REFUND_PUBKEY",
+              new PublicKey(rentRefundPubKey).toString()
+            );
           }
         }
       }
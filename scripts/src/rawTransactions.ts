@@ -9,7 +9,7 @@ import {
 import BN from "bn.js";
 import {
   SOLANA_CONNECTION,
-  LAYOUTS,
+  ESCROW_ACCOUNT_BASE_SPACE,
   ESCROW_PROGRAM_ID,
   ALICE_EXPECTED_USDT_TOKEN_AMOUNT,
 } from "./constants";
@@ -103,7 +103,7 @@ export async function createEscrowInitializeTransaction(
   try {
     // 1. Creating new escrow account IX
     const escrowAccount = new Keypair();
-    const space = LAYOUTS["escrowAccountLayout"].span;
+    const space = ESCROW_ACCOUNT_BASE_SPACE;
     const escrowAccountCreationIx = SystemProgram.createAccount({
       fromPubkey: owner,
       lamports: await SOLANA_CONNECTION.getMinimumBalanceForRentExemption(
@@ -5,4 +5,6 @@ export type EscrowLayout = {
   aliceYTokenPubKey: Uint8Array;
   expectedYTokenAmount: Uint8Array;
   escrowPDABump: Uint8Array;
+  yBeneficiaryPubKey: Uint8Array;
+  rentRefundPubKey: Uint8Array;
 };